@@ -1,57 +1,48 @@
-pub fn process_part1(input: &str) -> String {
-    input.split("\n\n")
-        .into_iter()
-        .map(|elf| 
-            elf.lines()
-                .map(|calories| calories.parse::<u32>().unwrap())
-                .sum::<u32>())
-        .max()
-        .unwrap_or(0)
-        .to_string()
-}
+use aoc_core::{Answer, Params, Result, Solution, parse_all};
+use aoc_parse::{blocks, lines_of, number};
 
-pub fn process_part2(input: &str) -> String {
-    let mut elf_calories = input.split("\n\n")
-        .into_iter()
-        .map(|elf| 
-            elf.lines()
-                .map(|calories| calories.parse::<u32>().unwrap())
-                .sum())
-        .collect::<Vec<u32>>();
-    elf_calories.sort_by(|a, b| b.cmp(a));
-    elf_calories
-        .iter()
-        .take(3)
-        .sum::<u32>()
-        .to_string()
-}
+pub struct Day01;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "1000
-2000
-3000
+impl Solution for Day01 {
+    type Input<'a> = Vec<u32>;
 
-4000
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let elves: Vec<Vec<u32>> = parse_all(input, blocks(lines_of(number)))?;
+        Ok(elves.iter()
+            .map(|calories| calories.iter().sum())
+            .collect())
+    }
 
-5000
-6000
+    fn part1(elf_calories: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(elf_calories.iter()
+            .max()
+            .copied()
+            .unwrap_or(0)
+            .into())
+    }
 
-7000
-8000
-9000
+    fn part2(elf_calories: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let mut elf_calories = elf_calories.clone();
+        elf_calories.sort_by(|a, b| b.cmp(a));
+        Ok(elf_calories
+            .iter()
+            .take(3)
+            .sum::<u32>()
+            .into())
+    }
+}
 
-10000";
+#[cfg(test)]
+mod tests {
+    use aoc_core::Part;
 
-    #[test]
-    fn part1_works() {
-        let result = process_part1(INPUT);
-        assert_eq!("24000", result);
-    }
+    use super::*;
+
+    aoc_core::examples!(Day01);
 
     #[test]
-    fn part2_works() {
-        assert_eq!("45000", process_part2(INPUT));
+    fn windows_line_endings_work() {
+        let input = "\u{feff}1000\r\n2000\r\n\r\n4000\r\n";
+        assert_eq!(4000, Day01::solve(input, Part::One).unwrap());
     }
 }
@@ -1,8 +1,11 @@
 use std::str::FromStr;
+
+use aoc_core::{Answer, Params, ParseError, Result, Solution};
+
 // ******************************************************************************
 // common
 #[derive(Clone, PartialEq, Eq)]
-enum Shape {
+pub enum Shape {
     Rock,
     Paper,
     Scissors,
@@ -32,7 +35,7 @@ impl FromStr for Shape {
 // ******************************************************************************
 // part 1
 
-struct Round1 {
+pub struct Round1 {
     action: Shape,
     response: Shape,
 }
@@ -84,45 +87,15 @@ impl Round1 {
     }
 }
 
-pub fn process_part1(input: &str) -> String {
-    input
-        .lines()
-        .map(|line| line.parse::<Round1>().unwrap())
-        // .map(|line| Round::parse(line).unwrap())
-        .map(|round| round.score())
-        .sum::<u32>()
-        .to_string()
-}
 // ******************************************************************************
 // part 2
-struct Round2 {
-    action: Shape,
-    result: RoundResult,
-}
-
-impl FromStr for RoundResult {
-    type Err = &'static str;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "X" => Ok(RoundResult::Lose),
-            "Y" => Ok(RoundResult::Draw),
-            "Z" => Ok(RoundResult::Win),
-            _ => Err("Not a valid RoundResult"),
-        }
-    }
-}
-
-impl FromStr for Round2 {
-    type Err = &'static str;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() != 3 {
-            return Err("Invalid line length");
-        }
-        let action = s[0..1].parse::<Shape>()?;
-        let result = s[2..3].parse::<RoundResult>()?;
-        Ok(Round2 { action, result })
+// in part 2 the second column is the desired outcome instead of our response
+fn desired_result(column: &Shape) -> RoundResult {
+    match column {
+        Shape::Rock => RoundResult::Lose,
+        Shape::Paper => RoundResult::Draw,
+        Shape::Scissors => RoundResult::Win,
     }
 }
 
@@ -138,33 +111,44 @@ fn response_shape_for_round_result(action: &Shape, result: RoundResult) -> Shape
     }
 }
 
-pub fn process_part2(input: &str) -> String {
-    input
-        .lines()
-        .map(|line| {
-            let Round2 { action, result } = line.parse::<Round2>().unwrap();
-            let response = response_shape_for_round_result(&action, result);
-            let round = Round1 { action, response };
-            round.score()
-        })
-        .sum::<u32>()
-        .to_string()
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input<'a> = Vec<Round1>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        input
+            .lines()
+            .map(|line| line.parse::<Round1>()
+                .map_err(|err| ParseError::at(input, line, format!("a round like \"A Y\" ({err})")).into()))
+            .collect()
+    }
+
+    fn part1(rounds: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(rounds
+            .iter()
+            .map(|round| round.score())
+            .sum::<u32>()
+            .into())
+    }
+
+    fn part2(rounds: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(rounds
+            .iter()
+            .map(|Round1 { action, response }| {
+                let result = desired_result(response);
+                let response = response_shape_for_round_result(action, result);
+                let round = Round1 { action: action.clone(), response };
+                round.score()
+            })
+            .sum::<u32>()
+            .into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "A Y
-B X
-C Z";
 
-    #[test]
-    fn part1_works() {
-        assert_eq!("15", process_part1(INPUT));
-    }
-
-    #[test]
-    fn part2_works() {
-        assert_eq!("12", process_part2(INPUT));
-    }
+    aoc_core::examples!(Day02);
 }
@@ -1,105 +1,104 @@
 use std::{collections::HashMap};
 
-fn item_value(item: &char) -> u32 {
+use aoc_core::{Answer, Error, Params, ParseError, Result, Solution};
+
+fn item_value(item: &char) -> Option<u32> {
     match *item {
-        'a'..='z' => *item as u32 - 'a' as u32 + 1,
-        'A'..='Z' => *item as u32 - 'A' as u32 + 27,
-        _ => panic!("Wrong character {item}"),
+        'a'..='z' => Some(*item as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(*item as u32 - 'A' as u32 + 27),
+        _ => None,
     } 
 }
 
 #[cfg(test)]
 #[test]
 fn item_value_works() {
-    assert_eq!(1, item_value(&'a'));
-    assert_eq!(26, item_value(&'z'));
-    assert_eq!(27, item_value(&'A'));
-    assert_eq!(52, item_value(&'Z'));
+    assert_eq!(Some(1), item_value(&'a'));
+    assert_eq!(Some(26), item_value(&'z'));
+    assert_eq!(Some(27), item_value(&'A'));
+    assert_eq!(Some(52), item_value(&'Z'));
+    assert_eq!(None, item_value(&'1'));
 }
 
-pub fn process_part1(input: &str) -> String {
-    input.lines()
-        .map(|line| {
-            let mut ruck: HashMap<char, u32> = HashMap::new();
-            let ruck_size = line.len() / 2;
-            let compartment1 = line[0..ruck_size].to_string();
-            let compartment2 = line[ruck_size..].to_string();
-            let mut duplicate = 'a';
-            compartment1.chars()
-                .for_each(|item| {
-                    let count = ruck.get(&item).unwrap_or(&0);
-                    ruck.insert(item, count + 1);
-                });
-            for item in compartment2.chars() {
-                if ruck.get(&item).is_some() {
-                    duplicate = item;
-                    break;
-                }
-            }
-            item_value(&duplicate)
-        })
-        .sum::<u32>()
-        .to_string()
-}
 // ******************************************************************************  
 // part 2
 
 use bit_set::{Bit, BitBuffer, BitSet};
 
-fn letter_to_bit_pos(c: char) -> u8 {
-    (item_value(&c) - 1) as u8
+fn letter_to_bit_pos(c: char) -> Option<u8> {
+    item_value(&c).map(|value| (value - 1) as u8)
 }
 
 fn backpack_to_item_set(line: &str) -> BitBuffer<u128> {
     let mut item_set = BitBuffer::default();
     line.chars()
-        .map(letter_to_bit_pos)
+        .filter_map(letter_to_bit_pos)
         .for_each(|b| item_set.set(b));
     item_set
 }
 
-pub fn process_part2(input: &str) -> String {
-    let mut elves = ["", "", ""];
-    let mut pos = 0;
-    let mut sum = 0;
-    for line in input.lines() {
-        elves[pos] = line;
-        if pos == 2 {
-            let common_items = elves.iter()
-                .map(|line| backpack_to_item_set(line))
-                .reduce(|a, b| a.intersection(&b))
-                .unwrap();
-
-            let item_pos = common_items
-                .into_iter()
-                .find(|(_, b)| *b == Bit::One)
-                .map(|t| t.0)
-                .unwrap();
-
-            sum += item_pos as u32 + 1; 
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        if let Some(pos) = input.find(|c: char| !c.is_ascii_alphabetic() && c != '\n') {
+            return Err(ParseError::at(input, &input[pos..], "an item letter").into());
         }
-        pos = (pos + 1) % 3;
-    };
-    sum.to_string()
+        Ok(input.lines().collect())
+    }
+
+    fn part1(rucksacks: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        rucksacks.iter()
+            .map(|line| {
+                let mut ruck: HashMap<char, u32> = HashMap::new();
+                let ruck_size = line.len() / 2;
+                let compartment1 = line[0..ruck_size].to_string();
+                let compartment2 = line[ruck_size..].to_string();
+                compartment1.chars()
+                    .for_each(|item| {
+                        let count = ruck.get(&item).unwrap_or(&0);
+                        ruck.insert(item, count + 1);
+                    });
+                compartment2.chars()
+                    .find(|item| ruck.contains_key(item))
+                    .and_then(|duplicate| item_value(&duplicate))
+                    .ok_or_else(|| Error::NoSolution(format!("no item is in both compartments of {line}")))
+            })
+            .sum::<Result<u32>>()
+            .map(Into::into)
+    }
+
+    fn part2(rucksacks: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let mut elves = ["", "", ""];
+        let mut pos = 0;
+        let mut sum = 0;
+        for line in rucksacks {
+            elves[pos] = line;
+            if pos == 2 {
+                let common_items = elves.iter()
+                    .map(|line| backpack_to_item_set(line))
+                    .reduce(|a, b| a.intersection(&b))
+                    .unwrap();
+
+                let item_pos = common_items
+                    .into_iter()
+                    .find(|(_, b)| *b == Bit::One)
+                    .map(|t| t.0)
+                    .ok_or_else(|| Error::NoSolution(format!("no badge is common to {}", elves.join(", "))))?;
+
+                sum += item_pos as u32 + 1; 
+            }
+            pos = (pos + 1) % 3;
+        };
+        Ok(sum.into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw";
-
-    #[test]
-    fn part1_works() {
-        assert_eq!("157", process_part1(INPUT));
-    }
 
-    #[test]
-    fn part2_works() {
-        assert_eq!("70", process_part2(INPUT));
-    }
+    aoc_core::examples!(Day03);
 }
@@ -1,10 +1,12 @@
-fn line_to_nums(line: &str) -> [u32;4] {
+use aoc_core::{Answer, Params, ParseError, Result, Solution};
+
+fn line_to_nums(line: &str) -> Option<[u32;4]> {
     line
         .split([',', '-'])
-        .map(|s| s.parse::<u32>().unwrap())
-        .collect::<Vec<u32>>()
+        .map(|s| s.parse::<u32>().ok())
+        .collect::<Option<Vec<u32>>>()?
         .try_into()
-        .unwrap()
+        .ok()
 }
 
 fn completely_overlapping(assignments: &[u32;4]) -> bool {
@@ -12,44 +14,41 @@ fn completely_overlapping(assignments: &[u32;4]) -> bool {
     (a >= c && b <= d) || (c >= a && d <= b)
 }
 
-pub fn process_part1(input: &str) -> String {
-    input.lines()
-        .map(line_to_nums)
-        .filter(completely_overlapping)
-        .count()
-        .to_string()
-}
-
 fn partially_overlapping(assignments: &[u32;4]) -> bool {
     let [a, b, c, d] = assignments;
     (b >= c && b <= d) || (d >= a && d <= b)
 }
 
-pub fn process_part2(input: &str) -> String {
-    input.lines()
-        .map(line_to_nums)
-        .filter(partially_overlapping)
-        .count()
-        .to_string()
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input<'a> = Vec<[u32; 4]>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        input.lines()
+            .map(|line| line_to_nums(line)
+                .ok_or_else(|| ParseError::at(input, line, "two ranges like \"2-4,6-8\"").into()))
+            .collect()
+    }
+
+    fn part1(assignments: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(assignments.iter()
+            .filter(|a| completely_overlapping(a))
+            .count()
+            .into())
+    }
+
+    fn part2(assignments: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(assignments.iter()
+            .filter(|a| partially_overlapping(a))
+            .count()
+            .into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "2-4,6-8
-2-3,4-5
-5-7,7-9
-2-8,3-7
-6-6,4-6
-2-6,4-8";
-
-    #[test]
-    fn part1_works() {
-        assert_eq!("2", process_part1(INPUT));
-    }
 
-    #[test]
-    fn part2_works() {
-        assert_eq!("4", process_part2(INPUT));
-    }
+    aoc_core::examples!(Day04);
 }
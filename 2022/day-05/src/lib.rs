@@ -3,94 +3,100 @@ mod parser;
 mod types;
 
 use crate::parser::{parse_move, parse_stacks};
-use crate::types::Stacks;
+use crate::types::{Move, Stacks};
+use aoc_core::{Answer, Error, Params, ParseError, Part, Result, Solution, Variant, parse_all_verbose};
+use nom::sequence::pair;
 
 fn top_of_stacks(stacks: Stacks) -> String {
     stacks.iter().flat_map(|s| s.last()).collect::<String>()
 }
 
-pub fn process_part1_own_parser(input: &str) -> String {
+pub fn part1_own_parser(input: &str) -> Result<Answer> {
     let mut lines = input.lines();
     let mut stacks = parse_stacks(&mut lines);
 
-    lines.for_each(|line| {
-        let m = parse_move(line);
-        (0..m.count).for_each(|_| {
-            let item = stacks[m.from - 1]
-                .pop()
-                .expect("bad move sequence, move from empty stack");
-            stacks[m.to - 1].push(item);
-        })
-    });
-    top_of_stacks(stacks)
+    for line in lines {
+        let m = parse_move(line)
+            .filter(|m| m.from > 0 && m.to > 0)
+            .ok_or_else(|| ParseError::at(input, line, "a move like \"move 1 from 2 to 3\""))?;
+        let m = Move { from: m.from - 1, to: m.to - 1, ..m };
+        move_crates(&mut stacks, &m, true)?;
+    }
+    Ok(top_of_stacks(stacks).into())
 }
 
-pub fn process_part1_nom_parser(input: &str) -> String {
-    let (input, mut stacks) = nom_parser::parse_stacks(input).expect("error parsing crates");
-    let (_, moves) = nom_parser::parse_moves(input).expect("error parsing moves");
-
-    for m in moves {
-        let from = &mut stacks[m.from];
-        let mut picked_up = from.split_off(from.len() - m.count);
+/// Moves the crates one by one with `reverse`, else all at once.
+fn move_crates(stacks: &mut Stacks, m: &Move, reverse: bool) -> Result<()> {
+    if m.from >= stacks.len() || m.to >= stacks.len() {
+        return Err(Error::NoSolution(format!("there are only {} stacks", stacks.len())));
+    }
+    let from = &mut stacks[m.from];
+    if from.len() < m.count {
+        return Err(Error::NoSolution(format!("stack {} has fewer than {} crates", m.from + 1, m.count)));
+    }
+    let mut picked_up = from.split_off(from.len() - m.count);
+    if reverse {
         picked_up.reverse();
-        let to = &mut stacks[m.to];
-        to.append(&mut picked_up);
     }
-    top_of_stacks(stacks)
+    stacks[m.to].append(&mut picked_up);
+    Ok(())
 }
 
-#[allow(non_upper_case_globals)]
-pub const process_part1: fn(&str) -> String = process_part1_nom_parser;
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input<'a> = (Stacks, Vec<Move>);
 
-pub fn process_part2_own_parser(input: &str) -> String {
-    let (input, mut stacks) = nom_parser::parse_stacks(input).expect("error parsing crates");
-    let (_, moves) = nom_parser::parse_moves(input).expect("error parsing moves");
+    const VARIANTS: &'static [Variant] = &[
+        Variant { name: "own_parser", part: Part::One, solve: |input, _| part1_own_parser(input) },
+    ];
 
-    for m in moves {
-        let from = &mut stacks[m.from];
-        let mut picked_up = from.split_off(from.len() - m.count);
-        let to = &mut stacks[m.to];
-        to.append(&mut picked_up);
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parse_all_verbose(input, pair(nom_parser::parse_stacks, nom_parser::parse_moves))
+            .map_err(Into::into)
     }
-    top_of_stacks(stacks)
-}
 
-pub fn process_part2_nom_parser(input: &str) -> String {
-    let (input, mut stacks) = nom_parser::parse_stacks(input).expect("error parsing crates");
-    let (_, moves) = nom_parser::parse_moves(input).expect("error parsing moves");
+    fn part1((stacks, moves): &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let mut stacks = stacks.clone();
+        for m in moves {
+            move_crates(&mut stacks, m, true)?;
+        }
+        Ok(top_of_stacks(stacks).into())
+    }
 
-    for m in moves {
-        let from = &mut stacks[m.from];
-        let mut picked_up = from.split_off(from.len() - m.count);
-        let to = &mut stacks[m.to];
-        to.append(&mut picked_up);
+    fn part2((stacks, moves): &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let mut stacks = stacks.clone();
+        for m in moves {
+            move_crates(&mut stacks, m, false)?;
+        }
+        Ok(top_of_stacks(stacks).into())
     }
-    top_of_stacks(stacks)
 }
 
-#[allow(non_upper_case_globals)]
-pub const process_part2: fn(&str) -> String = process_part2_nom_parser;
-
 #[cfg(test)]
 mod tests {
+    use aoc_core::Fixture;
+
     use super::*;
-    const INPUT: &str = "    [D]    
-[N] [C]    
-[Z] [M] [P]
-1   2   3 
 
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2";
+    aoc_core::examples!(Day05);
 
     #[test]
-    fn part1_works() {
-        assert_eq!("CMZ", process_part1(INPUT));
+    fn part1_own_parser_works() {
+        let fixture = Fixture::parse("example.txt", include_str!("../fixtures/example.txt")).unwrap();
+        assert_eq!("CMZ", part1_own_parser(&fixture.input).unwrap());
     }
 
     #[test]
-    fn part2_works() {
-        assert_eq!("MCD", process_part2(INPUT));
+    fn bad_moves_are_errors() {
+        let input = "[A]\n 1 \n\nmove 2 from 1 to 1";
+        let no_solution = Error::NoSolution("stack 1 has fewer than 2 crates".to_string());
+        assert_eq!(Err(no_solution.clone()), Day05::solve(input, Part::One));
+        assert_eq!(Err(no_solution), part1_own_parser(input));
+
+        let err = Day05::solve("[A]\n 1 \n\nmove 1 from 0 to 1", Part::One).unwrap_err();
+        assert_eq!("invalid input: line 4, column 13: expected a stack number, found \"0 to 1\"", err.to_string());
+        let err = part1_own_parser("[A]\n 1 \n\nmove 1 to 1").unwrap_err();
+        assert_eq!("invalid input: line 4, column 1: expected a move like \"move 1 from 2 to 3\", found \"move 1 to 1\"", err.to_string());
     }
 }
@@ -1,15 +1,19 @@
 use crate::types::{Move, Stacks};
 use nom::branch::alt;
+use nom::combinator::verify;
 use nom::character::complete::{self, alpha1, digit1, multispace1, newline, space0};
+use nom::error::{context, VerboseError};
 use nom::multi::{many1, separated_list1};
 use nom::sequence::{delimited, preceded};
-use nom::{bytes::complete::tag, IResult};
+use nom::bytes::complete::tag;
 
-fn parse_crate(input: &str) -> IResult<&str, Option<char>> {
-    let (input, c) = alt((
+type IResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
+
+fn parse_crate(input: &str) -> IResult<'_, Option<char>> {
+    let (input, c) = context("a crate like \"[A]\" or three spaces", alt((
         tag("   "),
         delimited(complete::char('['), alpha1, complete::char(']')),
-    ))(input)?;
+    )))(input)?;
     let result = match c {
         "   " => None,
         value => Some(value.chars().next().unwrap()),
@@ -17,21 +21,19 @@ fn parse_crate(input: &str) -> IResult<&str, Option<char>> {
     Ok((input, result))
 }
 
-fn parse_crate_line(input: &str) -> IResult<&str, Vec<Option<char>>> {
+fn parse_crate_line(input: &str) -> IResult<'_, Vec<Option<char>>> {
     let (input, crates) = separated_list1(tag(" "), parse_crate)(input)?;
 
     Ok((input, crates))
 }
 
-pub fn parse_stacks(input: &str) -> IResult<&str, Stacks> {
-    let mut result: Stacks = Vec::new();
+pub fn parse_stacks(input: &str) -> IResult<'_, Stacks> {
     let (input, mut layers) = separated_list1(newline, parse_crate_line)(input)?;
     let (input, _) = newline(input)?;
-    let (input, _numbers) = many1(preceded(space0, digit1))(input)?;
+    let (input, _numbers) = context("a line of stack numbers", many1(preceded(space0, digit1)))(input)?;
     layers.reverse();
-    for _ in 0..layers[0].len() {
-        result.push(Vec::new());
-    }
+    let stack_count = layers.iter().map(Vec::len).max().unwrap_or(0);
+    let mut result: Stacks = vec![Vec::new(); stack_count];
 
     for layer in layers {
         for (idx, c) in layer.iter().enumerate() {
@@ -44,13 +46,15 @@ pub fn parse_stacks(input: &str) -> IResult<&str, Stacks> {
     Ok((input, result))
 }
 
-fn parse_move(input: &str) -> IResult<&str, Move> {
-    let (input, _) = tag("move ")(input)?;
-    let (input, count) = complete::u32(input)?;
-    let (input, _) = tag(" from ")(input)?;
-    let (input, from) = complete::u32(input)?;
-    let (input, _) = tag(" to ")(input)?;
-    let (input, to) = complete::u32(input)?;
+fn parse_move(input: &str) -> IResult<'_, Move> {
+    let (input, _) = context("a move like \"move 1 from 2 to 3\"", tag("move "))(input)?;
+    let (input, count) = context("a number of crates", complete::u32)(input)?;
+    // stacks are numbered from 1
+    let stack = || context("a stack number", verify(complete::u32, |&n| n > 0));
+    let (input, _) = context("\" from \"", tag(" from "))(input)?;
+    let (input, from) = stack()(input)?;
+    let (input, _) = context("\" to \"", tag(" to "))(input)?;
+    let (input, to) = stack()(input)?;
     let result = Move {
         from: from as usize - 1,
         to: to as usize - 1,
@@ -59,7 +63,7 @@ fn parse_move(input: &str) -> IResult<&str, Move> {
     Ok((input, result))
 }
 
-pub fn parse_moves(input: &str) -> IResult<&str, Vec<Move>> {
+pub fn parse_moves(input: &str) -> IResult<'_, Vec<Move>> {
     let (input, moves) = separated_list1(newline, parse_move)(input)?;
     Ok((input, moves))
 }
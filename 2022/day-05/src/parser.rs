@@ -5,8 +5,8 @@ fn parse_crate_line(line: &str, stacks: &mut Stacks) {
     let mut chars = line.chars();
     (0..stacks.len())
         .for_each(|i| {
-            let item = chars.nth(1).expect("bad crate input");
-            if item.is_uppercase() { // ignoring line of stack indices
+            // a line may stop early when the stacks to its right are lower
+            if let Some(item) = chars.nth(1).filter(|item| item.is_uppercase()) { // ignoring line of stack indices
                 stacks[i].push(item);
             }
             chars.nth(1);
@@ -33,24 +33,32 @@ pub fn parse_stacks(lines: &mut Lines) -> Stacks {
     stacks
 }
 
-pub fn parse_move(line: &str) -> Move {
+/// `None` unless the line reads `move <count> from <stack> to <stack>`.
+pub fn parse_move(line: &str) -> Option<Move> {
     let parts = line.split(' ')
         .collect::<Vec<&str>>();
-    let values = [1, 3, 5].map(|idx| parts[idx]
-        .parse::<usize>()
-        .expect("bad move input"));
-    Move {
-        count: values[0],
-        from: values[1],
-        to: values[2],
+    if parts.len() != 6 || parts[0] != "move" || parts[2] != "from" || parts[4] != "to" {
+        return None;
     }
+    let mut values = [1, 3, 5].into_iter().map(|idx| parts[idx].parse::<usize>().ok());
+    Some(Move {
+        count: values.next()??,
+        from: values.next()??,
+        to: values.next()??,
+    })
 }
 
 #[test]
 fn parse_move_works() {
     let input = "move 13 from 3 to 6";
-    let m = parse_move(input);
+    let m = parse_move(input).unwrap();
     assert_eq!(13, m.count);
     assert_eq!(3, m.from);
     assert_eq!(6, m.to);
 }
+
+#[test]
+fn parse_move_rejects_other_lines() {
+    assert!(parse_move("move 13 from 3").is_none());
+    assert!(parse_move("move x from 3 to 6").is_none());
+}
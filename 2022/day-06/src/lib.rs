@@ -2,6 +2,7 @@ mod cyclic_buffer;
 
 use std::collections::HashSet;
 use crate::cyclic_buffer::CyclicBuffer;
+use aoc_core::{Answer, Error, Params, Part, Result, Solution, Variant};
 
 // todo: this is sufficient, but quite naive implementation. By holding previous n-1 information,
 // this check could be done more efficiently
@@ -13,75 +14,80 @@ fn is_unique<const N: usize>(window: &CyclicBuffer<char, N>) -> bool {
     unique_signals.len() == N
 }
 
-fn get_start_signal_position<const N: usize>(input: &str) -> usize {
+fn get_start_signal_position<const N: usize>(input: &str) -> Option<usize> {
     let mut chars = input.chars();
     let mut init: [char; N] = ['_'; N];
 
-    (0..N).for_each(|i| init[i] = chars.next().expect("input too short"));
+    for slot in init.iter_mut() {
+        *slot = chars.next()?;
+    }
     let mut window = CyclicBuffer::<char, N>::new(init);
     if is_unique(&window) {
-        return N;
+        return Some(N);
     }
 
     for (pos, c) in chars.enumerate() {
         window.push(c);
         if is_unique(&window) {
-            return pos + N + 1;
+            return Some(pos + N + 1);
         };
     }
-    0 
-}
-
-pub fn process_part1(input: &str) -> String {
-    let pos = get_start_signal_position::<4>(input);
-    pos.to_string()
+    None
 }
 
-pub fn process_part2(input: &str) -> String {
-    // let pos = get_start_signal_position::<14>(input);
-    // pos.to_string()
-    let window_size = 14;
-    let chars = input.chars().collect::<Vec<char>>();
+fn get_start_signal_position_windows(signal: &str, window_size: usize) -> Option<usize> {
+    let chars = signal.chars().collect::<Vec<char>>();
 
     let indexed_window = chars.windows(window_size)
         .enumerate()
         .find(|(_i, win)| {
             let s = win.iter().collect::<HashSet<&char>>();
             s.len() == window_size
-        })
-        .unwrap();
-    (indexed_window.0 + window_size).to_string()
+        })?;
+    Some(indexed_window.0 + window_size)
+}
+
+/// The answer for a marker of `size` distinct characters, if the signal has one.
+fn marker(position: Option<usize>, size: usize) -> Result<Answer> {
+    position
+        .map(Into::into)
+        .ok_or_else(|| Error::NoSolution(format!("no {size} characters in a row are all different")))
 }
 
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input<'a> = &'a str;
+
+    const VARIANTS: &'static [Variant] = &[
+        Variant { name: "windows", part: Part::One, solve: |signal, _| marker(get_start_signal_position_windows(signal, 4), 4) },
+        Variant { name: "cyclic_buffer", part: Part::Two, solve: |signal, _| marker(get_start_signal_position::<14>(signal), 14) },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(input)
+    }
+
+    fn part1(signal: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        marker(get_start_signal_position::<4>(signal), 4)
+    }
+
+    fn part2(signal: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        marker(get_start_signal_position_windows(signal, 14), 14)
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const TEST_DATA1: [(&str, u32);4] = [
-        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5),
-        ("nppdvjthqldpwncqszvftbrmjlhg", 6),
-        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10),
-        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11),
-    ];
 
-    #[test]
-    fn part1_works() {
-        for (input, expected_output) in TEST_DATA1 {
-            assert_eq!(expected_output.to_string(), process_part1(input));
-        }
-    }
+    aoc_core::examples!(Day06);
 
-    const TEST_DATA2: [(&str, u32);5] = [
-        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 19),
-        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 23),
-        ("nppdvjthqldpwncqszvftbrmjlhg", 23),
-        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 29),
-        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 26),
-    ];
     #[test]
-    fn part2_works() {
-        for (input, expected_output) in TEST_DATA2 {
-            assert_eq!(expected_output.to_string(), process_part2(input));
-        }
+    fn missing_marker_is_an_error() {
+        let no_solution = Err(Error::NoSolution("no 4 characters in a row are all different".to_string()));
+        assert_eq!(no_solution, Day06::solve("abc", Part::One));
+        assert_eq!(no_solution, Day06::solve("abcabcabc", Part::One));
+        assert_eq!(no_solution, Day06::solve_variant("abc", Part::One, "windows", &Params::default()));
     }
 }
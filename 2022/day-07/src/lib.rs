@@ -1,13 +1,13 @@
 use std::collections::{BTreeMap, BTreeSet};
 
+use aoc_core::{Answer, Error, Param, Params, Result, Solution, parse_all_verbose};
 use parser::parse_terminal;
 use types::{Cmd, LsOutput, Path};
 
 mod parser;
 mod types;
 
-fn calculate_dir_sizes(input: &str) -> BTreeMap<String, usize> {
-    let (_, cmds) = parse_terminal(input).expect("failed to parse input");
+fn calculate_dir_sizes(cmds: &[Cmd]) -> BTreeMap<String, usize> {
     let mut dir_sizes = BTreeMap::new();
     let mut dir_lsed = BTreeSet::<String>::new();
     let mut current_dir =  vec!["/"];
@@ -45,7 +45,7 @@ fn calculate_dir_sizes(input: &str) -> BTreeMap<String, usize> {
                         current_dir.pop();
                     }
                     Path::Name(name) => {
-                        current_dir.push(name);
+                        current_dir.push(*name);
                     }
                 }
             }
@@ -54,65 +54,72 @@ fn calculate_dir_sizes(input: &str) -> BTreeMap<String, usize> {
     dir_sizes
 }
 
-pub fn process_part1(input: &str) -> String {
-    calculate_dir_sizes(input).iter()
-        .map(|(_dir, size)| *size)
-        .filter(|size| *size <= 100000)
-        .sum::<usize>()
-        .to_string()
-}
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input<'a> = Vec<Cmd<'a>>;
+
+    const PARAMS: &'static [Param] = &[
+        Param { name: "total_space", default: "70000000", about: "size of the disk" },
+        Param { name: "needed_space", default: "30000000", about: "free space the update needs" },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let cmds = parse_all_verbose(input, parse_terminal)?;
+        Ok(cmds)
+    }
+
+    fn part1(cmds: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(calculate_dir_sizes(cmds).values()
+            .copied()
+            .filter(|size| *size <= 100000)
+            .sum::<usize>()
+            .into())
+    }
+
+    fn part2(cmds: &Self::Input<'_>, params: &Params) -> Result<Answer> {
+        let total_space: usize = params.get("total_space")?;
+        let needed_space: usize = params.get("needed_space")?;
 
-pub fn process_part2(input: &str) -> String {
-    const TOTAL_SPACE: usize = 70000000;
-    const NEEDED_SPACE: usize = 30000000;
-
-    let dir_sizes = calculate_dir_sizes(input);
-    let root_dir_size = dir_sizes.get("/").unwrap();
-    let avail = TOTAL_SPACE - root_dir_size;
-    let to_free = NEEDED_SPACE - avail;
-
-    dir_sizes.iter()
-        .map(|(_dir, size)| *size)
-        .filter(|size| *size >= to_free)
-        .min()
-        .expect("unable to find directory to delete")
-        .to_string()
+        let dir_sizes = calculate_dir_sizes(cmds);
+        let root_dir_size = dir_sizes.get("/")
+            .ok_or_else(|| Error::NoSolution("the log never lists the root directory".to_string()))?;
+        let avail = total_space.checked_sub(*root_dir_size)
+            .ok_or_else(|| Error::NoSolution("the files do not fit on the disk".to_string()))?;
+        let to_free = needed_space.saturating_sub(avail);
+
+        dir_sizes.values()
+            .copied()
+            .filter(|size| *size >= to_free)
+            .min()
+            .map(Into::into)
+            .ok_or_else(|| Error::NoSolution("unable to find directory to delete".to_string()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_core::Part;
+
     use super::*;
-    const INPUT: &str = "$ cd /
-$ ls
-dir a
-14848514 b.txt
-8504156 c.dat
-dir d
-$ cd a
-$ ls
-dir e
-29116 f
-2557 g
-62596 h.lst
-$ cd e
-$ ls
-584 i
-$ cd ..
-$ cd ..
-$ cd d
-$ ls
-4060174 j
-8033020 d.log
-5626152 d.ext
-7214296 k";
+
+    aoc_core::examples!(Day07);
+
+    #[test]
+    fn parse_accepts_dots_in_dir_names() {
+        assert_eq!(14, Day07::solve("$ cd /\n$ ls\ndir a.b\n$ cd a.b\n$ ls\n7 c", Part::One).unwrap());
+    }
 
     #[test]
-    fn part1_works() {
-        assert_eq!("95437", process_part1(INPUT));
+    fn parse_reports_bad_lines() {
+        let err = Day07::parse("$ cd /\n$ ls\n12 a\n$ rm a").unwrap_err();
+        assert_eq!("invalid input: line 4, column 1: expected a command like \"$ cd a\" or \"$ ls\", found \"$ rm a\"", err.to_string());
+        let err = Day07::parse("$ cd /\n$ ls\nfile a").unwrap_err();
+        assert_eq!("invalid input: line 3, column 1: expected a file like \"123 a.txt\" or a directory like \"dir a\", found \"file a\"", err.to_string());
     }
 
     #[test]
-    fn part2_works() {
-        assert_eq!("24933642", process_part2(INPUT));
+    fn log_without_ls_is_an_error() {
+        assert!(matches!(Day07::solve("$ cd a", Part::Two), Err(Error::NoSolution(_))));
     }
 }
@@ -1,20 +1,23 @@
-use nom::{IResult, multi::separated_list1, character::complete::{newline, alpha1, not_line_ending, space1, self}, branch::alt, bytes::complete::tag, sequence::preceded};
+use aoc_parse::lines_of;
+use nom::{multi::separated_list1, character::complete::{newline, not_line_ending, space1, self}, branch::alt, bytes::complete::tag, sequence::preceded, error::{context, VerboseError}};
 
 use crate::types::{Cmd, LsOutput, Path};
 
-pub fn parse_terminal(input: &str) -> IResult<&str, Vec<Cmd>> {
-    let (input, cmds) = separated_list1(newline, parse_cmd)(input)?;
+type IResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
+
+pub fn parse_terminal(input: &str) -> IResult<'_, Vec<Cmd<'_>>> {
+    let (input, cmds) = lines_of(parse_cmd)(input)?;
     Ok((input, cmds)) 
 }
 
-fn parse_cmd(input: &str) -> IResult<&str, Cmd> {
-    let (input, cmd) = alt((parse_cmd_cd, parse_cmd_ls))(input)?;
+fn parse_cmd(input: &str) -> IResult<'_, Cmd<'_>> {
+    let (input, cmd) = context("a command like \"$ cd a\" or \"$ ls\"", alt((parse_cmd_cd, parse_cmd_ls)))(input)?;
     Ok((input, cmd))
 }
 
-fn parse_cmd_cd(input: &str) -> IResult<&str, Cmd> {
+fn parse_cmd_cd(input: &str) -> IResult<'_, Cmd<'_>> {
     let (input, _) = tag("$ cd ")(input)?;
-    let (input, path_str) = alt((tag("/"), tag(".."), alpha1))(input)?;
+    let (input, path_str) = not_line_ending(input)?;
     let path = match path_str {
         "/" => Path::Root,
         ".." => Path::Up,
@@ -23,7 +26,7 @@ fn parse_cmd_cd(input: &str) -> IResult<&str, Cmd> {
     Ok((input, Cmd::Cd(path)))
 }
 
-fn parse_cmd_ls(input: &str) -> IResult<&str, Cmd> {
+fn parse_cmd_ls(input: &str) -> IResult<'_, Cmd<'_>> {
     let (input, _) = tag("$ ls")(input)?;
     let (input, _) = newline(input)?;
     let (input, ls_outs) = separated_list1(newline, parse_cmd_ls_out_line)(input)?; 
@@ -31,23 +34,23 @@ fn parse_cmd_ls(input: &str) -> IResult<&str, Cmd> {
     Ok((input, res))
 }
 
-fn parse_cmd_ls_out_line(input: &str) -> IResult<&str, LsOutput> {
-    let (input, ls_out) = alt((
+fn parse_cmd_ls_out_line(input: &str) -> IResult<'_, LsOutput<'_>> {
+    let (input, ls_out) = context("a file like \"123 a.txt\" or a directory like \"dir a\"", alt((
         parse_cmd_ls_out_file,
         parse_cmd_ls_out_dir
-    ))(input)?;
+    )))(input)?;
     Ok((input, ls_out))
 }
 
-fn parse_cmd_ls_out_file(input: &str) -> IResult<&str, LsOutput> {
+fn parse_cmd_ls_out_file(input: &str) -> IResult<'_, LsOutput<'_>> {
     let (input, size) = complete::u32(input)?;
     let (input, name) = preceded(space1, not_line_ending)(input)?;
     Ok((input, LsOutput::File(name, size as usize)))
 }
 
-fn parse_cmd_ls_out_dir(input: &str) -> IResult<&str, LsOutput> {
+fn parse_cmd_ls_out_dir(input: &str) -> IResult<'_, LsOutput<'_>> {
     let (input, _) = tag("dir ")(input)?;
-    let (input, name) = alpha1(input)?;
+    let (input, name) = not_line_ending(input)?;
     Ok((input, LsOutput::Dir(name)))
 }
 
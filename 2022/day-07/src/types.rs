@@ -5,6 +5,7 @@ pub enum Path<'a> {
     Name(&'a str),
 }
 
+#[allow(dead_code)] // names are only kept for debugging
 #[derive(Debug)]
 pub enum LsOutput<'a> {
     File(&'a str, usize),
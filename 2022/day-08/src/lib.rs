@@ -1,5 +1,8 @@
 use std::cmp::max;
 
+use aoc_core::{Answer, Params, Result, Solution};
+use grid::{Grid, Pos};
+
 #[derive(Debug)]
 struct Node {
     height: i32,
@@ -42,52 +45,14 @@ impl Node {
     }
 }
 
-pub fn process_part1(input: &str) -> String {
-    let size = input.lines()
-        .next()
-        .expect("empty input")
-        .len();
-
-    // a buffer with tree nodes, from top top left to bottom right
-    let mut nodes: Vec<Node> = input.chars()
-        .filter(|&c| c.is_ascii_digit())
-        .map(|c| Node::new(c as i32 - '0' as i32 + 1))
-        .collect();
-
-    // skip first row and column
-    (size..nodes.len())
-        .filter(|idx| idx % size != 0)
-        .for_each(|idx| {
-            let node_left = &nodes[idx - 1];
-            let node_up = &nodes[idx - size];
-            let left = max(node_left.height, node_left.left);
-            let up = max(node_up.height, node_up.up);
-            nodes[idx].left =  left;
-            nodes[idx].up = up;
-
-            let rev_idx = nodes.len() - idx - 1;
-            let node_right = &nodes[rev_idx + 1];
-            let node_down = &nodes[rev_idx + size];
-            let right = max(node_right.height, node_right.right);
-            let down = max(node_down.height, node_down.down);
-            nodes[rev_idx].right = right;
-            nodes[rev_idx].down =  down;
-        });
-
-    nodes.iter()
-        .filter(|&node| node.is_visible())
-        .count()
-        .to_string()
-}
-
-fn count_visible_trees<I: Iterator<Item=usize>>(nodes: &[Node], height: i32, iter: I) -> i32 {
-   let indices: Vec<usize> = iter.collect(); 
+fn count_visible_trees<I: Iterator<Item=Pos>>(nodes: &Grid<Node>, height: i32, iter: I) -> i32 {
+   let positions: Vec<Pos> = iter.collect();
 
-   let counted = indices.iter()
-        .take_while(|&&i| nodes[i].height < height)
+   let counted = positions.iter()
+        .take_while(|&&pos| nodes[pos].height < height)
         .count() as i32;
     
-    if counted == indices.len() as i32 {
+    if counted == positions.len() as i32 {
         // when we reach border, we don't see any further
         counted
     } else {
@@ -96,58 +61,76 @@ fn count_visible_trees<I: Iterator<Item=usize>>(nodes: &[Node], height: i32, ite
     }
 }
 
-pub fn process_part2(input: &str) -> String {
-    let size = input.lines()
-        .next()
-        .expect("empty input")
-        .len();
-
-    // a buffer with tree nodes, from top top left to bottom right
-    let mut nodes: Vec<Node> = input.chars()
-        .filter(|&c| c.is_ascii_digit())
-        .map(|c| Node::new2(c as i32 - '0' as i32 + 1))
-        .collect();
-
-    // skip first and last rows and columns
-    (size..nodes.len())
-        .filter(|idx| {
-            let x = idx % size;
-            x != 0 && x != size - 1
-        })
-        .for_each(|idx| {
-            let x = idx % size;
-            let y = idx / size;
-
-            let height = nodes[idx].height;
-            nodes[idx].left = count_visible_trees(&nodes, height, ((y * size)..idx).rev());
-            nodes[idx].right = count_visible_trees(&nodes, height, (idx + 1)..((y + 1) * size));
-            nodes[idx].up = count_visible_trees(&nodes, height, (0..y).rev().map(|i| i * size + x));
-            nodes[idx].down = count_visible_trees(&nodes, height, ((y + 1)..size).map(|i| i * size + x));
-        });
-
-    nodes.iter()
-        .map(|node| node.scenic_score())
-        .max()
-        .unwrap()
-        .to_string()
+/// Tree heights, from top left to bottom right.
+pub type Forest = Grid<i32>;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input<'a> = Forest;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(Grid::parse(input, |c| c.to_digit(10).map(|d| d as i32 + 1))?)
+    }
+
+    fn part1(forest: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let (width, height) = (forest.width(), forest.height());
+        let mut nodes = forest.map(|&height| Node::new(height));
+
+        // skip first row and column, and last ones when going backwards
+        for y in 1..height {
+            for x in 1..width {
+                let pos = Pos::new(x, y);
+                let node_left = &nodes[Pos::new(x - 1, y)];
+                let node_up = &nodes[Pos::new(x, y - 1)];
+                let left = max(node_left.height, node_left.left);
+                let up = max(node_up.height, node_up.up);
+                nodes[pos].left = left;
+                nodes[pos].up = up;
+
+                let rev_pos = Pos::new(width - x - 1, height - y - 1);
+                let node_right = &nodes[Pos::new(rev_pos.x + 1, rev_pos.y)];
+                let node_down = &nodes[Pos::new(rev_pos.x, rev_pos.y + 1)];
+                let right = max(node_right.height, node_right.right);
+                let down = max(node_down.height, node_down.down);
+                nodes[rev_pos].right = right;
+                nodes[rev_pos].down = down;
+            }
+        }
+
+        Ok(nodes.iter()
+            .filter(|(_, node)| node.is_visible())
+            .count()
+            .into())
+    }
+
+    fn part2(forest: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let (width, height) = (forest.width(), forest.height());
+        let mut nodes = forest.map(|&height| Node::new2(height));
+
+        // skip first and last rows and columns
+        for y in 1..height.saturating_sub(1) {
+            for x in 1..width.saturating_sub(1) {
+                let pos = Pos::new(x, y);
+                let tree = nodes[pos].height;
+                nodes[pos].left = count_visible_trees(&nodes, tree, (0..x).rev().map(|x| Pos::new(x, y)));
+                nodes[pos].right = count_visible_trees(&nodes, tree, (x + 1..width).map(|x| Pos::new(x, y)));
+                nodes[pos].up = count_visible_trees(&nodes, tree, (0..y).rev().map(|y| Pos::new(x, y)));
+                nodes[pos].down = count_visible_trees(&nodes, tree, (y + 1..height).map(|y| Pos::new(x, y)));
+            }
+        }
+
+        Ok(nodes.iter()
+            .map(|(_, node)| node.scenic_score())
+            .max()
+            .unwrap()
+            .into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "30373
-25512
-65332
-33549
-35390";
-
-    #[test]
-    fn part1_works() {
-        assert_eq!("21", process_part1(INPUT));
-    }
 
-    #[test]
-    fn part2_works() {
-        assert_eq!("8", process_part2(INPUT));
-    }
+    aoc_core::examples!(Day08);
 }
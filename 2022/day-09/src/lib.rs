@@ -1,38 +1,35 @@
-use std::{collections::BTreeSet, iter::repeat, cmp::Ordering};
+use std::{collections::BTreeSet, iter::repeat_n};
 
-use nom::{IResult, multi::separated_list1, character::complete::{newline, char, one_of, self}, sequence::separated_pair};
+use aoc_core::{Answer, Params, Result, Solution, parse_all_verbose};
+use aoc_parse::lines_of;
+use geom::{Dir4, Point2};
+use nom::{character::complete::{char, self}, sequence::separated_pair, branch::alt, combinator::value, error::{context, VerboseError}};
 
-#[derive(Clone)]
-enum Dir {
-    Left,
-    Right,
-    Up,
-    Down,
-}
+type IResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
 
-struct Move {
-    dir: Dir,
+pub struct Move {
+    dir: Dir4,
     dist: u32,
 }
 
-type Pos = (i32, i32);
+type Pos = Point2<i32>;
 
-fn parse_moves(input: &str) -> IResult<&str, Vec<Move>> {
-    let (input, moves) = separated_list1(newline, parse_move)(input)?;
+fn parse_moves(input: &str) -> IResult<'_, Vec<Move>> {
+    let (input, moves) = lines_of(parse_move)(input)?;
 
     Ok((input, moves))
 }
 
-fn parse_move(input: &str) -> IResult<&str, Move> {
-    let (input, (dir, dist)) = separated_pair(one_of("LRUD"), char(' '), complete::u32)(input)?;
+fn parse_move(input: &str) -> IResult<'_, Move> {
+    let dir = context("a direction U, D, R or L", alt((
+        value(Dir4::Up, char('U')),
+        value(Dir4::Down, char('D')),
+        value(Dir4::Right, char('R')),
+        value(Dir4::Left, char('L')),
+    )));
+    let dist = context("a number of steps", complete::u32);
+    let (input, (dir, dist)) = separated_pair(dir, char(' '), dist)(input)?;
 
-    let dir = match dir {
-        'U' => Dir::Up,
-        'D' => Dir::Down,
-        'R' => Dir::Right,
-        'L' => Dir::Left,
-        _ => unreachable!("Bro, what a move!") // todo: handle error properly
-    };
     let m = Move {
         dir,
         dist,
@@ -40,112 +37,88 @@ fn parse_move(input: &str) -> IResult<&str, Move> {
     Ok((input, m))
 }
 
-fn calculate_step(pos: Pos, dir: &Dir) -> Pos {
-    match dir {
-        Dir::Up => (pos.0, pos.1 + 1),
-        Dir::Down => (pos.0, pos.1 - 1),
-        Dir::Left => (pos.0 - 1, pos.1),
-        Dir::Right => (pos.0 + 1, pos.1),
-    }
+fn calculate_catch_up(h: Pos, t: Pos) -> Pos {
+    if h.chebyshev(t) >= 2 {
+        t + (h - t).signum()
+    } else {
+        t
+    } 
 }
 
-pub fn process_part1(input: &str) -> String {
-    struct State {
-        h: Pos, 
-        t: Pos,
-        trail: BTreeSet<Pos>,
-    }
+pub struct Day09;
 
-    let state = State {
-        h: (0, 0),
-        t: (0, 0),
-        trail: BTreeSet::new(),
-    };
+impl Solution for Day09 {
+    type Input<'a> = Vec<Move>;
 
-    let (_input, moves) = parse_moves(input).unwrap();
-    moves.iter()
-        .flat_map(|Move {dir, dist}| repeat(dir).take(*dist as usize))
-        .fold(state, |mut state, dir| {
-            let new_h = calculate_step(state.h, dir);
-            let new_t = calculate_catch_up(new_h, state.t);
-            state.trail.insert(new_t);
-            State {
-                h: new_h,
-                t: new_t,
-                trail: state.trail,
-            }
-        }).trail
-        .len()
-        .to_string()
-}
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let moves = parse_all_verbose(input, parse_moves)?;
+        Ok(moves)
+    }
 
-fn calculate_catch_up(h: Pos, t: Pos) -> Pos {
-    fn normalize(d: i32) -> i32 {
-        match d.cmp(&0) {
-            Ordering::Less => -1,
-            Ordering::Equal => 0,
-            Ordering::Greater => 1,
+    fn part1(moves: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        struct State {
+            h: Pos, 
+            t: Pos,
+            trail: BTreeSet<Pos>,
         }
-    }
-    let dx = h.0 - t.0;
-    let dy = h.1 - t.1;
-    let adx = dx.abs(); 
-    let ady = dy.abs();
 
-    if adx >= 2 || ady >= 2 {
-        (t.0 + normalize(dx), t.1 + normalize(dy))
-    } else {
-        t
-    } 
-}
+        let state = State {
+            h: Pos::ORIGIN,
+            t: Pos::ORIGIN,
+            trail: BTreeSet::new(),
+        };
+
+        Ok(moves.iter()
+            .flat_map(|Move {dir, dist}| repeat_n(dir, *dist as usize))
+            .fold(state, |mut state, dir| {
+                let new_h = state.h + dir.offset();
+                let new_t = calculate_catch_up(new_h, state.t);
+                state.trail.insert(new_t);
+                State {
+                    h: new_h,
+                    t: new_t,
+                    trail: state.trail,
+                }
+            }).trail
+            .len()
+            .into())
+    }
 
-pub fn process_part2(input: &str) -> String {
-    const N: usize = 10;
-    let mut rope = [(0, 0); N];
-    let mut trail: BTreeSet<Pos> = BTreeSet::new();
-    let (_input, moves) = parse_moves(input).unwrap();
-
-    moves.iter()
-        .flat_map(|Move {dir, dist}| repeat(dir).take(*dist as usize))
-        .for_each(|dir| {
-            rope[0] = calculate_step(rope[0], dir);
-            (1..N).for_each(|i| {
-                rope[i] = calculate_catch_up(rope[i - 1], rope[i]);
+    fn part2(moves: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        const N: usize = 10;
+        let mut rope = [Pos::ORIGIN; N];
+        let mut trail: BTreeSet<Pos> = BTreeSet::new();
+
+        moves.iter()
+            .flat_map(|Move {dir, dist}| repeat_n(dir, *dist as usize))
+            .for_each(|dir| {
+                rope[0] += dir.offset();
+                (1..N).for_each(|i| {
+                    rope[i] = calculate_catch_up(rope[i - 1], rope[i]);
+                });
+                trail.insert(rope[N - 1]);
             });
-            trail.insert(rope[N - 1]);
-        });
-        trail
-        .len()
-        .to_string()
+        Ok(trail
+            .len()
+            .into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_core::Part;
+
     use super::*;
-    const INPUT1: &str = "R 4
-U 4
-L 3
-D 1
-R 4
-D 1
-L 5
-R 2";
 
-    #[test]
-    fn part1_works() {
-        assert_eq!("13", process_part1(INPUT1));
-    }
+    aoc_core::examples!(Day09);
 
-const INPUT2: &str = "R 5
-U 8
-L 8
-D 3
-R 17
-D 10
-L 25
-U 20";
     #[test]
-    fn part2_works() {
-        assert_eq!("36", process_part2(INPUT2));
+    fn parse_reports_bad_move() {
+        let err = Day09::solve("X 2\nR 4", Part::One).unwrap_err();
+        assert_eq!("invalid input: line 1, column 1: expected a direction U, D, R or L, found \"X 2\"", err.to_string());
+        let err = Day09::solve("R 4\nU x", Part::One).unwrap_err();
+        assert_eq!("invalid input: line 2, column 3: expected a number of steps, found \"x\"", err.to_string());
+        let err = Day09::solve("R 4\nU_2", Part::One).unwrap_err();
+        assert_eq!("invalid input: line 2, column 2: expected ' ', found \"_2\"", err.to_string());
     }
 }
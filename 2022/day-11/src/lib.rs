@@ -1,3 +1,4 @@
+use aoc_core::{Answer, Error, Params, Result, Solution, parse_all_verbose};
 use parser::parse_input;
 use types::{Monkey, Operand, Operation};
 
@@ -34,97 +35,93 @@ impl Monkey {
         throw_target as usize
     }
 }
-pub fn process_part1(input: &str) -> String {
-    let (_, mut monkeys) = parse_input(input).unwrap();
-    let lcm = monkeys.iter().fold(1, |prod, m| m.test * prod);
-    for _ in 0..20 {
-        for monkey_idx in 0..monkeys.len() {
-            for _ in 0..monkeys[monkey_idx].items.len() {
-                let monkey = monkeys.get_mut(monkey_idx).unwrap();
-                let item = monkey.inspect_item(true, lcm);
-                let throw_to = monkey.throw_to(item);
-                monkeys.get_mut(throw_to).unwrap()
-                    .items
-                    .push_back(item);
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let monkeys = parse_all_verbose(input, parse_input)?;
+        for monkey in &monkeys {
+            if let Some(target) = [monkey.throw_true, monkey.throw_false].into_iter().find(|&t| t as usize >= monkeys.len()) {
+                return Err(Error::NoSolution(format!("monkey {} throws to monkey {target}, but there are only {}", monkey.id, monkeys.len())));
             }
         }
+        Ok(monkeys)
     }
 
-    monkeys
-        .sort_by(|a, b| b.inspect_count.cmp(&a.inspect_count));
-    monkeys
-        .iter()
-        .map(|m| m.inspect_count)
-        .take(2)
-        .product::<u64>()
-        .to_string()
-}
+    fn part1(monkeys: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let mut monkeys = monkeys.clone();
+        let lcm = monkeys.iter().fold(1, |prod, m| m.test * prod);
+        for _ in 0..20 {
+            for monkey_idx in 0..monkeys.len() {
+                for _ in 0..monkeys[monkey_idx].items.len() {
+                    let monkey = &mut monkeys[monkey_idx];
+                    let item = monkey.inspect_item(true, lcm);
+                    let throw_to = monkey.throw_to(item);
+                    monkeys[throw_to]
+                        .items
+                        .push_back(item);
+                }
+            }
+        }
+
+        monkeys
+            .sort_by_key(|m| std::cmp::Reverse(m.inspect_count));
+        Ok(monkeys
+            .iter()
+            .map(|m| m.inspect_count)
+            .take(2)
+            .product::<u64>()
+            .into())
+    }
 
-pub fn process_part2(input: &str) -> String {
-    let (_, mut monkeys) = parse_input(input).unwrap();
-    let lcm = monkeys.iter().fold(1, |prod, m| m.test * prod);
+    fn part2(monkeys: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let mut monkeys = monkeys.clone();
+        let lcm = monkeys.iter().fold(1, |prod, m| m.test * prod);
 
-    for _ in 0..10000 {
-        for monkey_idx in 0..monkeys.len() {
-            for _ in 0..monkeys[monkey_idx].items.len() {
-                let monkey = monkeys.get_mut(monkey_idx).unwrap();
-                let item = monkey.inspect_item(false, lcm);
-                let throw_to = monkey.throw_to(item);
-                monkeys.get_mut(throw_to).unwrap()
-                    .items
-                    .push_back(item);
+        for _ in 0..10000 {
+            for monkey_idx in 0..monkeys.len() {
+                for _ in 0..monkeys[monkey_idx].items.len() {
+                    let monkey = &mut monkeys[monkey_idx];
+                    let item = monkey.inspect_item(false, lcm);
+                    let throw_to = monkey.throw_to(item);
+                    monkeys[throw_to]
+                        .items
+                        .push_back(item);
+                }
             }
         }
-    }
 
-    monkeys
-        .sort_by(|a, b| b.inspect_count.cmp(&a.inspect_count));
-    monkeys
-        .iter()
-        .map(|m| m.inspect_count)
-        .take(2)
-        .product::<u64>()
-        .to_string()
+        monkeys
+            .sort_by_key(|m| std::cmp::Reverse(m.inspect_count));
+        Ok(monkeys
+            .iter()
+            .map(|m| m.inspect_count)
+            .take(2)
+            .product::<u64>()
+            .into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "Monkey 0:
-  Starting items: 79, 98
-  Operation: new = old * 19
-  Test: divisible by 23
-    If true: throw to monkey 2
-    If false: throw to monkey 3
-
-Monkey 1:
-  Starting items: 54, 65, 75, 74
-  Operation: new = old + 6
-  Test: divisible by 19
-    If true: throw to monkey 2
-    If false: throw to monkey 0
 
-Monkey 2:
-  Starting items: 79, 60, 97
-  Operation: new = old * old
-  Test: divisible by 13
-    If true: throw to monkey 1
-    If false: throw to monkey 3
-
-Monkey 3:
-  Starting items: 74
-  Operation: new = old + 3
-  Test: divisible by 17
-    If true: throw to monkey 0
-    If false: throw to monkey 1";
+    aoc_core::examples!(Day11);
 
     #[test]
-    fn part1_works() {
-        assert_eq!("10605", process_part1(INPUT));
-    }
+    fn parse_reports_what_was_expected() {
+        let monkey = "Monkey 0:\n  Starting items: 79\n  Operation: new = old * 19\n  Test: divisible by 23\n    If true: throw to monkey 0\n    If false: throw to monkey 0";
+        assert!(Day11::parse(monkey).is_ok());
 
-    #[test]
-    fn part2_works() {
-        assert_eq!("2713310158", process_part2(INPUT));
+        let err = Day11::parse(&monkey.replace("old * 19", "old / 19")).unwrap_err();
+        assert_eq!("invalid input: line 3, column 24: expected an operator * or +, found \"/ 19\"", err.to_string());
+        let err = Day11::parse(&format!("{monkey}\n\n{}", monkey.replace("Test", "Check"))).unwrap_err();
+        assert_eq!("invalid input: line 11, column 1: expected \"  Test: divisible by \", found \"  Check: divisible by 23\"", err.to_string());
+        let err = Day11::parse(&monkey.replace("divisible by 23", "divisible by 0")).unwrap_err();
+        assert_eq!("invalid input: line 4, column 22: expected a divisor above 0, found \"0\"", err.to_string());
+        let err = Day11::parse(&monkey.replace("monkey 0", "monkey 1")).unwrap_err();
+        assert_eq!("no solution: monkey 0 throws to monkey 1, but there are only 1", err.to_string());
     }
 }
@@ -1,27 +1,41 @@
 use std::collections::VecDeque;
 
-use nom::{IResult, character::complete::{newline, self, char, multispace0}, multi::{separated_list1, many1}, bytes::complete::tag, sequence::{preceded, delimited}, branch::alt, Parser};
+use aoc_parse::{blocks, number_list};
+use nom::{character::complete::{newline, self, char, multispace0}, bytes::complete::tag, sequence::{preceded, delimited}, branch::alt, combinator::verify, error::{context, VerboseError}, Parser};
 
 use crate::types::{Monkey, Operation, Operand};
 
-pub fn parse_input(input: &str) -> IResult<&str, Vec<Monkey>> {
-    let (input, monkeys) = separated_list1(many1(newline), parse_monkey)(input)?;
+type IResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
+
+pub fn parse_input(input: &str) -> IResult<'_, Vec<Monkey>> {
+    let (input, monkeys) = blocks(parse_monkey)(input)?;
     Ok((input, monkeys))
 }
 
-fn parse_monkey(input: &str) -> IResult<&str, Monkey> {
-    let (input, _) = tag("Monkey ")(input)?;
-    let (input, id) = complete::u64(input)?;
+fn parse_monkey(input: &str) -> IResult<'_, Monkey> {
+    let (input, _) = context("\"Monkey \"", tag("Monkey "))(input)?;
+    let (input, id) = context("a monkey number", complete::u64)(input)?;
     let (input, _) = preceded(tag(":"), newline)(input)?;
-    let (input, items) = preceded(tag("  Starting items: ") , separated_list1(tag(", "), complete::u64))(input)?;
+    let (input, _) = context("\"  Starting items: \"", tag("  Starting items: "))(input)?;
+    let (input, items) = context("a list of worry levels", number_list(","))(input)?;
     let (input, _) = newline(input)?;
-    let (input, operation) = preceded(tag("  Operation: new = "), parse_operation)(input)?;
+    let (input, _) = context("\"  Operation: new = \"", tag("  Operation: new = "))(input)?;
+    let (input, operation) = parse_operation(input)?;
     let (input, _) = newline(input)?;
-    let (input, test) = preceded(tag("  Test: divisible by "), complete::u64)(input)?;
+    let (input, test) = preceded(
+        context("\"  Test: divisible by \"", tag("  Test: divisible by ")),
+        context("a divisor above 0", verify(complete::u64, |&n| n > 0)),
+    )(input)?;
     let (input, _) = newline(input)?;
-    let (input, throw_true) = preceded(tag("    If true: throw to monkey "), complete::u64)(input)?;
+    let (input, throw_true) = preceded(
+        context("\"    If true: throw to monkey \"", tag("    If true: throw to monkey ")),
+        context("a monkey number", complete::u64),
+    )(input)?;
     let (input, _) = newline(input)?;
-    let (input, throw_false) = preceded(tag("    If false: throw to monkey "), complete::u64)(input)?;
+    let (input, throw_false) = preceded(
+        context("\"    If false: throw to monkey \"", tag("    If false: throw to monkey ")),
+        context("a monkey number", complete::u64),
+    )(input)?;
 
 
     let items = VecDeque::from(items);
@@ -36,11 +50,11 @@ fn parse_monkey(input: &str) -> IResult<&str, Monkey> {
     }))
 }
 
-fn parse_operation(input: &str) -> IResult<&str, Operation> {
+fn parse_operation(input: &str) -> IResult<'_, Operation> {
     let (input, operand1) = parse_operand(input)?;
     let (input, op) = delimited(
         multispace0,
-        alt((char('*'), char('+'))),
+        context("an operator * or +", alt((char('*'), char('+')))),
         multispace0)
         (input)?;
     let (input, operand2) = parse_operand(input)?;
@@ -53,10 +67,10 @@ fn parse_operation(input: &str) -> IResult<&str, Operation> {
     Ok((input, operation))
 }
 
-fn parse_operand(input: &str) -> IResult<&str, Operand> {
-    let (input, result) = alt((
+fn parse_operand(input: &str) -> IResult<'_, Operand> {
+    let (input, result) = context("\"old\" or a number", alt((
         tag("old").map(|_| Operand::Old),
         complete::u64.map(Operand::Value)
-    ))(input)?;
+    )))(input)?;
     Ok((input, result))
 }
@@ -12,8 +12,9 @@ pub enum Operation {
     Add(Operand, Operand),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Monkey {
+    #[allow(dead_code)]
     pub id: u64,
     pub items: VecDeque<u64>,
     pub operation: Operation,
@@ -1,15 +1,20 @@
 use std::cmp::Ordering;
 
+use aoc_core::{Answer, Params, Result, Solution, parse_all_verbose};
+use aoc_parse::blocks;
 use nom::{
     branch::alt,
-    character::complete::{self, char, newline},
-    multi::{separated_list0, separated_list1},
-    sequence::{delimited, pair, separated_pair},
-    IResult, Parser,
+    character::complete::{self, char, line_ending},
+    error::{context, VerboseError},
+    multi::separated_list0,
+    sequence::{delimited, separated_pair},
+    Parser,
 };
 
+type IResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
+
 #[derive(Debug, Eq)]
-enum Packet {
+pub enum Packet {
     List(Vec<Packet>),
     Int(u32),
 }
@@ -44,88 +49,75 @@ impl Ord for Packet {
     }
 }
 
-fn parse_input(input: &str) -> IResult<&str, Vec<(Packet, Packet)>> {
-    let (input, pairs) = separated_list1(
-        pair(newline, newline),
-        separated_pair(packet, newline, packet),
-    )(input)?;
+fn parse_input(input: &str) -> IResult<'_, Vec<(Packet, Packet)>> {
+    let pair = separated_pair(packet, context("a second packet on the next line", line_ending), packet);
+    let (input, pairs) = blocks(pair)(input)?;
 
     Ok((input, pairs))
 }
 
-fn packet(input: &str) -> IResult<&str, Packet> {
-    let (input, p) = alt((
-        delimited(char('['), separated_list0(char(','), packet), char(']')).map(Packet::List),
+fn packet(input: &str) -> IResult<'_, Packet> {
+    let list = delimited(char('['), separated_list0(char(','), packet), context("',' or ']'", char(']')));
+    // alt only keeps the error of its last branch, and a list is where the details are
+    let (input, p) = context("a list or a number", alt((
         complete::u32.map(Packet::Int),
-    ))(input)?;
+        list.map(Packet::List),
+    )))(input)?;
 
     Ok((input, p))
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_input, packet_pairs) = parse_input(input).expect("Input parse failed");
-    packet_pairs
-        .iter()
-        .enumerate()
-        .filter_map(|(idx, (left, right))| if left < right { Some(idx + 1) } else { None })
-        .sum::<usize>()
-        .to_string()
-}
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input<'a> = Vec<(Packet, Packet)>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let packet_pairs = parse_all_verbose(input, parse_input)?;
+        Ok(packet_pairs)
+    }
 
-pub fn process_part2(input: &str) -> String {
-    let div1: Packet = Packet::List(vec![Packet::List(vec![Packet::Int(2)])]);
-    let div2: Packet = Packet::List(vec![Packet::List(vec![Packet::Int(6)])]);
-
-    let (_input, packet_pairs) = parse_input(input).expect("Input parse failed");
-    let mut packets = packet_pairs
-        .iter()
-        .flat_map(|(left, right)| [left, right])
-        .chain([&div1, &div2])
-        .collect::<Vec<&Packet>>();
-    packets.sort();
-    packets
-        .iter()
-        .enumerate()
-        .filter(|(_idx, p)| [&div1, &div2].contains(p))
-        .map(|(idx, _)| idx + 1)
-        .product::<usize>()
-        .to_string()
+    fn part1(packet_pairs: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(packet_pairs
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, (left, right))| if left < right { Some(idx + 1) } else { None })
+            .sum::<usize>()
+            .into())
+    }
+
+    fn part2(packet_pairs: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let div1: Packet = Packet::List(vec![Packet::List(vec![Packet::Int(2)])]);
+        let div2: Packet = Packet::List(vec![Packet::List(vec![Packet::Int(6)])]);
+        let mut packets = packet_pairs
+            .iter()
+            .flat_map(|(left, right)| [left, right])
+            .chain([&div1, &div2])
+            .collect::<Vec<&Packet>>();
+        packets.sort();
+        Ok(packets
+            .iter()
+            .enumerate()
+            .filter(|(_idx, p)| [&div1, &div2].contains(p))
+            .map(|(idx, _)| idx + 1)
+            .product::<usize>()
+            .into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "[1,1,3,1,1]
-[1,1,5,1,1]
-
-[[1],[2,3,4]]
-[[1],4]
 
-[9]
-[[8,7,6]]
-
-[[4,4],4,4]
-[[4,4],4,4,4]
-
-[7,7,7,7]
-[7,7,7]
-
-[]
-[3]
-
-[[[]]]
-[[]]
-
-[1,[2,[3,[4,[5,6,7]]]],8,9]
-[1,[2,[3,[4,[5,6,0]]]],8,9]";
-
-    #[test]
-    fn part1_works() {
-        assert_eq!("13", process_part1(INPUT));
-    }
+    aoc_core::examples!(Day13);
 
     #[test]
-    fn part2_works() {
-        assert_eq!("140", process_part2(INPUT));
+    fn parse_reports_what_was_expected() {
+        let err = Day13::parse("[1]\n[2]\n\n[1;2]\n[3]").unwrap_err();
+        assert_eq!("invalid input: line 4, column 3: expected ',' or ']', found \";2]\"", err.to_string());
+        let err = Day13::parse("[1]\nx").unwrap_err();
+        assert_eq!("invalid input: line 2, column 1: expected a list or a number, found \"x\"", err.to_string());
+        let err = Day13::parse("[1]\n[2]\n\n[3]").unwrap_err();
+        assert_eq!("invalid input: line 4, column 4: expected a second packet on the next line, found end of input", err.to_string());
     }
 }
@@ -1,13 +1,14 @@
-
 use std::{collections::BTreeSet, ops::Range};
 
-use nom::{IResult, multi::separated_list1, character::complete::{line_ending, self}, sequence::{preceded, separated_pair},  bytes::complete::tag, Parser};
+use aoc_core::{Answer, Error, Param, Params, Result, Solution, parse_all};
+use geom::Point2;
+use aoc_parse::{key_value, lines_of, number};
+use nom::{IResult, sequence::{preceded, separated_pair},  bytes::complete::tag, Parser};
 
-#[derive(Debug)]
-struct Pos(i64, i64);
+type Pos = Point2<i64>;
 
 #[derive(Debug)]
-struct Reading {
+pub struct Reading {
     sensor: Pos,
     beacon: Pos,
     distance: i64,
@@ -16,7 +17,7 @@ struct Reading {
 pub fn merge_ranges_in_place<T: Ord + Copy>(ranges: &mut Vec<Range<T>>) {
     ranges.sort_by_key(|r| r.start);
     let mut i = 0;
-    while i < ranges.len() - 1 {
+    while i + 1 < ranges.len() {
         let r1 = &ranges[i];
         let r2 = &ranges[i + 1];
         if r1.end >= r2.start {
@@ -29,30 +30,20 @@ pub fn merge_ranges_in_place<T: Ord + Copy>(ranges: &mut Vec<Range<T>>) {
 }
 
 fn pos(input: &str) -> IResult<&str, Pos> {
-    let (input, pos) = 
-    separated_pair(
-        preceded(tag("x="), complete::i64),
-        tag(", "),
-        preceded(tag("y="), complete::i64)
-    )(input)?;
+    let (input, pos) = separated_pair(key_value("x", number), tag(", "), key_value("y", number))(input)?;
   
-    Ok((input, Pos(pos.0, pos.1)))
-}
-
-fn distance(pos1: &Pos, pos2: &Pos) -> i64 {
-    (pos1.0 - pos2.0).abs() + (pos1.1 - pos2.1).abs()
+    Ok((input, pos.into()))
 }
 
 fn readings(input: &str) -> IResult<&str, Vec<Reading>> {
     let (input, readings) =
-    separated_list1(
-        line_ending,
+    lines_of(
         separated_pair(
             preceded(tag("Sensor at "), pos),
             tag(": "),
             preceded(tag("closest beacon is at "), pos)
         ).map(|(pos1, pos2)| {
-                let distance = distance(&pos1, &pos2);
+                let distance = pos1.manhattan(pos2);
                 Reading {
                     sensor: pos1,
                     beacon: pos2,
@@ -66,10 +57,10 @@ fn readings(input: &str) -> IResult<&str, Vec<Reading>> {
 fn no_beacon_ranges(readings: &[Reading], target_row: i64) -> Vec<Range<i64>> {
     let mut no_beacon_ranges = readings.iter()
         .filter_map(|reading| {
-            let y_dist = (reading.sensor.1 - target_row).abs();
+            let y_dist = (reading.sensor.y - target_row).abs();
             let x_span = reading.distance - y_dist;
             if x_span >= 0 {
-                let x = reading.sensor.0;
+                let x = reading.sensor.x;
                 Some((x - x_span)..(x + x_span + 1))
             } else {
                 None
@@ -80,15 +71,13 @@ fn no_beacon_ranges(readings: &[Reading], target_row: i64) -> Vec<Range<i64>> {
     no_beacon_ranges
 }
 
-fn part1(input: &str, target_row: i64) -> String {
-    let (_input, readings) = readings(input).unwrap();
-
-    let no_beacon_ranges = no_beacon_ranges(&readings, target_row);
+fn no_beacon_count(readings: &[Reading], target_row: i64) -> i64 {
+    let no_beacon_ranges = no_beacon_ranges(readings, target_row);
 
     let beacons_on_row: BTreeSet<i64> = readings.iter()
         .map(|reading| &reading.beacon)
-        .filter(|beacon| beacon.1 == target_row)
-        .map(|beacon| beacon.0)
+        .filter(|beacon| beacon.y == target_row)
+        .map(|beacon| beacon.x)
         .collect();
 
     let no_beacons_count = no_beacon_ranges.iter()
@@ -99,62 +88,66 @@ fn part1(input: &str, target_row: i64) -> String {
         .filter(|x| no_beacon_ranges.iter().any(|range| range.contains(x)))
         .count() as i64;
 
-    (no_beacons_count - beacons_in_range)
-        .to_string()
-}
-
-pub fn process_part1(input: &str) -> String {
-    const TARGET_ROW: i64 = 2_000_000;
-    part1(input, TARGET_ROW)
+    no_beacons_count - beacons_in_range
 }
 
-fn part2(input: &str, coord_upper_bound: i64) -> String {
-    let (_input, readings) = readings(input).unwrap();
+fn tuning_frequency(readings: &[Reading], coord_upper_bound: i64) -> Option<i64> {
     let test_range = 0..=coord_upper_bound;
     // todo: could run below in parallel
     for y in test_range.clone() {
-        let no_beacon_ranges = no_beacon_ranges(&readings, y);
+        let no_beacon_ranges = no_beacon_ranges(readings, y);
         let found = no_beacon_ranges.iter()
             .flat_map(|r| [r.start - 1, r.end])
             .find(|x| test_range.contains(x));
 
         if let Some(x) = found {
-            return (x * 4_000_000 + y).to_string();
+            return Some(x * 4_000_000 + y);
         }
     }
 
-    panic!("not found");
+    None
 }
 
-pub fn process_part2(input: &str) -> String {
-    part2(input, 4_000_000)
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input<'a> = Vec<Reading>;
+
+    const PARAMS: &'static [Param] = &[
+        Param { name: "row", default: "2000000", about: "row to count the positions without a beacon in" },
+        Param { name: "bound", default: "4000000", about: "largest x and y the distress beacon can be at" },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let readings = parse_all(input, readings)?;
+        Ok(readings)
+    }
+
+    fn part1(readings: &Self::Input<'_>, params: &Params) -> Result<Answer> {
+        let row = params.get("row")?;
+        Ok(no_beacon_count(readings, row).into())
+    }
+
+    fn part2(readings: &Self::Input<'_>, params: &Params) -> Result<Answer> {
+        let bound = params.get("bound")?;
+        tuning_frequency(readings, bound)
+            .map(Into::into)
+            .ok_or_else(|| Error::NoSolution("there is no spot left for the distress beacon".to_string()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_core::Part;
+
     use super::*;
-    const INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
-Sensor at x=9, y=16: closest beacon is at x=10, y=16
-Sensor at x=13, y=2: closest beacon is at x=15, y=3
-Sensor at x=12, y=14: closest beacon is at x=10, y=16
-Sensor at x=10, y=20: closest beacon is at x=10, y=16
-Sensor at x=14, y=17: closest beacon is at x=10, y=16
-Sensor at x=8, y=7: closest beacon is at x=2, y=10
-Sensor at x=2, y=0: closest beacon is at x=2, y=10
-Sensor at x=0, y=11: closest beacon is at x=2, y=10
-Sensor at x=20, y=14: closest beacon is at x=25, y=17
-Sensor at x=17, y=20: closest beacon is at x=21, y=22
-Sensor at x=16, y=7: closest beacon is at x=15, y=3
-Sensor at x=14, y=3: closest beacon is at x=15, y=3
-Sensor at x=20, y=1: closest beacon is at x=15, y=3";
 
-    #[test]
-    fn part1_works() {
-        assert_eq!("26", part1(INPUT, 10));
-    }
+    aoc_core::examples!(Day15);
 
     #[test]
-    fn part2_works() {
-        assert_eq!("56000011", part2(INPUT, 20));
+    fn row_without_sensors_works() {
+        let input = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
+        let params: Params = [("row", "100000000")].into_iter().collect();
+        assert_eq!(0, Day15::solve_with(input, Part::One, &params).unwrap());
     }
 }
@@ -1,23 +1,18 @@
-use std::collections::BTreeSet;
-#[allow(unused)]
-use std::{collections::{BTreeMap, HashMap, VecDeque, HashSet}, fs::File, io::Write};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 
+use aoc_core::{Answer, Params, ParseError, Result, Solution, parse_all, tracing::trace};
 use nom::{IResult, sequence::{delimited, preceded, tuple}, character::complete::{self, alpha1, line_ending}, bytes::complete::tag, multi::separated_list1,  branch::alt};
-#[allow(unused)]
-use petgraph::{prelude::DiGraphMap, algo::dijkstra, dot::{Dot, Config}};
-#[allow(unused)]
 use itertools::Itertools;
-#[allow(unused)]
-use rayon::prelude::ParallelIterator;
+use search::bfs_all;
 
 #[derive(Debug)]
-struct Node<'a> {
+pub struct Node<'a> {
     name: &'a str,
     rate: u32,
     tunnels: Vec<&'a str>,
 }
 
-fn node(input: &str) -> IResult<&str, Node> {
+fn node(input: &str) -> IResult<&str, Node<'_>> {
     let (input, name) = delimited(tag("Valve "), alpha1, tag(" has flow rate="))(input)?;
     let (input, rate) = complete::u32(input)?;
     let (input, tunnels) = preceded(
@@ -40,29 +35,31 @@ fn node(input: &str) -> IResult<&str, Node> {
 type Vertex<'a> = (&'a str, u32);
 type DistanceMap<'a> = HashMap<(Vertex<'a>, Vertex<'a>), i32>; 
 
-fn build_graph<'a>(node_list: &'a [Node], nodes: &BTreeMap<&str, &Node>) -> DiGraphMap<(&'a str, u32), ()>{
-    let edges: Vec<(Vertex, Vertex)> = node_list.iter()
-        .flat_map(|n| {
-            n.tunnels.iter()
-                .map(|&m_name| {
-                    let m = nodes.get(m_name).unwrap();
-                    (
-                        (n.name, n.rate),
-                        (m_name, m.rate)
-                    )
-                })
+type Graph<'a> = BTreeMap<Vertex<'a>, Vec<Vertex<'a>>>;
+
+fn build_graph<'a>(node_list: &'a [Node], nodes: &BTreeMap<&str, &Node>) -> Graph<'a> {
+    let graph: Graph = node_list.iter()
+        .map(|n| {
+            let tunnels = n.tunnels.iter()
+                .map(|&m_name| (m_name, nodes[m_name].rate))
+                .collect();
+            ((n.name, n.rate), tunnels)
         })
         .collect();
 
-    DiGraphMap::<(&str, u32), ()>::from_edges(&edges)
-    // let dot = Dot::with_config(&graph, &[Config::EdgeNoLabel]);
-    // println!("{:?}", Dot::with_config(&graph, &[Config::EdgeNoLabel]));
-    // let mut file = File::create("graph.dot").unwrap();
-    // file.write_all(format!("{:?}", dot).as_bytes()).unwrap();
+    trace!("valve graph in dot format:\n{}", to_dot(&graph));
+    graph
+}
+
+fn to_dot(graph: &Graph) -> String {
+    let edges: String = graph.iter()
+        .flat_map(|(from, tunnels)| tunnels.iter().map(move |to| format!("    \"{from:?}\" -> \"{to:?}\"\n")))
+        .collect();
+    format!("digraph {{\n{edges}}}")
 }
 
-fn calculate_distances<'a>(start_node: &Vertex<'a>, pos_vertices: &Vec<Vertex<'a>>, zero_vertices: &Vec<Vertex<'a>>, graph: &'a DiGraphMap<(&str, u32), ()>) -> DistanceMap<'a> {
-    let mut pos_vertices = pos_vertices.clone();
+fn calculate_distances<'a>(start_node: &Vertex<'a>, pos_vertices: &[Vertex<'a>], zero_vertices: &[Vertex<'a>], graph: &Graph<'a>) -> DistanceMap<'a> {
+    let mut pos_vertices = pos_vertices.to_vec();
     let sources = if start_node.1 > 0 {
         pos_vertices
     } else {
@@ -73,20 +70,12 @@ fn calculate_distances<'a>(start_node: &Vertex<'a>, pos_vertices: &Vec<Vertex<'a
 
     let mut distances: DistanceMap = HashMap::new();
     sources.iter().for_each(|s| {
-        let mut res = dijkstra(
-            &graph,
-            *s,
-            None,
-            |_| 1,
-        );
-
-        zero_vertices.iter()
-            .for_each(|v| {
-                res.remove(v);
-            });
+        let res = bfs_all([*s], |v| graph.get(v).into_iter().flatten().copied());
+
         res.iter()
+            .filter(|(v, _)| !zero_vertices.contains(v))
             .for_each(|(k, v)| {
-                distances.insert((*s, *k), *v);
+                distances.insert((*s, *k), v as i32);
             });
     });
     distances
@@ -120,7 +109,11 @@ fn calculate_max_release<'a>(start_node: Vertex<'a>, pos_vertices: Vec<Vertex<'a
         for i in 0..state.remaining_valves.len() {
             let mut new_valves = state.remaining_valves.clone();
             let next_valve = new_valves.remove(i);
-            let time_needed = distances.get(&(state.current_valve, next_valve)).unwrap() + 1;
+            // valves that can't be reached from here can't be opened either
+            let Some(distance) = distances.get(&(state.current_valve, next_valve)) else {
+                continue;
+            };
+            let time_needed = distance + 1;
             if time_needed < state.remaining_time {
                 let next_state = State {
                     remaining_time: state.remaining_time - time_needed,
@@ -140,81 +133,85 @@ fn calculate_max_release<'a>(start_node: Vertex<'a>, pos_vertices: Vec<Vertex<'a
     relieved_max
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_input, node_list) = separated_list1(line_ending, node)(input).unwrap();
-    let nodes: BTreeMap<&str, &Node> = node_list.iter()
-        .map(|n| (n.name, n))
-        .collect();
+pub struct Day16;
 
-    let start_node = nodes.get("AA")
-        .map(|n| (n.name, n.rate))
-        .unwrap();
+impl Solution for Day16 {
+    type Input<'a> = Vec<Node<'a>>;
 
-    let graph = build_graph(&node_list, &nodes);
-    let (pos_vertices, zero_vertices): (Vec<Vertex>, Vec<Vertex>) = node_list.iter()
-        .map(|n| (n.name, n.rate))
-        .partition(|n| n.1 > 0);
-    let distances = calculate_distances(&start_node, &pos_vertices, &zero_vertices, &graph);
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let node_list = parse_all(input, separated_list1(line_ending, node))?;
 
+        // every tunnel has to lead to a valve in the list, so the lookups below can't fail
+        let names: HashSet<&str> = node_list.iter().map(|n| n.name).collect();
+        if let Some(missing) = node_list.iter().flat_map(|n| &n.tunnels).find(|name| !names.contains(*name)) {
+            return Err(ParseError::at(input, missing, "the name of a valve in the list").into());
+        }
+        if !names.contains("AA") {
+            return Err(ParseError::at(input, &input[input.len()..], "a valve named AA").into());
+        }
+        Ok(node_list)
+    }
 
-    calculate_max_release(start_node, pos_vertices, &distances, 30).to_string()
-}
+    fn part1(node_list: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let nodes: BTreeMap<&str, &Node> = node_list.iter()
+            .map(|n| (n.name, n))
+            .collect();
 
-pub fn process_part2(input: &str) -> String {
-    let (_input, node_list) = separated_list1(line_ending, node)(input).unwrap();
-    let nodes: BTreeMap<&str, &Node> = node_list.iter()
-        .map(|n| (n.name, n))
-        .collect();
+        let start_node = (nodes["AA"].name, nodes["AA"].rate);
 
-    let start_node = nodes.get("AA")
-        .map(|n| (n.name, n.rate))
-        .unwrap();
+        let graph = build_graph(node_list, &nodes);
+        let (pos_vertices, zero_vertices): (Vec<Vertex>, Vec<Vertex>) = node_list.iter()
+            .map(|n| (n.name, n.rate))
+            .partition(|n| n.1 > 0);
+        let distances = calculate_distances(&start_node, &pos_vertices, &zero_vertices, &graph);
 
-    let graph = build_graph(&node_list, &nodes);
-    let (pos_vertices, zero_vertices): (Vec<Vertex>, Vec<Vertex>) = node_list.iter()
-        .map(|n| (n.name, n.rate))
-        .partition(|n| n.1 > 0);
-    let distances = calculate_distances(&start_node, &pos_vertices, &zero_vertices, &graph);
-   
-    let valve_count = pos_vertices.len();
-    let all_valves = BTreeSet::from_iter(pos_vertices.iter());
-    let mut max_relieved = 0;
-    for k in 2..=(valve_count / 2) {
-        all_valves.iter().combinations(k)
-            .for_each(|valves1| {
-                let valves1: BTreeSet<&Vertex> = valves1.iter().copied().copied().collect();
-                let valves2: BTreeSet<&Vertex> = all_valves.difference(&valves1).copied().collect();
-                let relieved1 = calculate_max_release(start_node, valves1.iter().copied().copied().collect(), &distances, 26);
-                let relieved2 = calculate_max_release(start_node, valves2.iter().copied().copied().collect(), &distances, 26);
-                max_relieved = max_relieved.max(relieved1 + relieved2);
-            });
+
+        Ok(calculate_max_release(start_node, pos_vertices, &distances, 30).into())
     }
+
+    fn part2(node_list: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let nodes: BTreeMap<&str, &Node> = node_list.iter()
+            .map(|n| (n.name, n))
+            .collect();
+
+        let start_node = (nodes["AA"].name, nodes["AA"].rate);
+
+        let graph = build_graph(node_list, &nodes);
+        let (pos_vertices, zero_vertices): (Vec<Vertex>, Vec<Vertex>) = node_list.iter()
+            .map(|n| (n.name, n.rate))
+            .partition(|n| n.1 > 0);
+        let distances = calculate_distances(&start_node, &pos_vertices, &zero_vertices, &graph);
+   
+        let valve_count = pos_vertices.len();
+        let all_valves = BTreeSet::from_iter(pos_vertices.iter());
+        let mut max_relieved = 0;
+        for k in 2..=(valve_count / 2) {
+            all_valves.iter().combinations(k)
+                .for_each(|valves1| {
+                    let valves1: BTreeSet<&Vertex> = valves1.iter().copied().copied().collect();
+                    let valves2: BTreeSet<&Vertex> = all_valves.difference(&valves1).copied().collect();
+                    let relieved1 = calculate_max_release(start_node, valves1.iter().copied().copied().collect(), &distances, 26);
+                    let relieved2 = calculate_max_release(start_node, valves2.iter().copied().copied().collect(), &distances, 26);
+                    max_relieved = max_relieved.max(relieved1 + relieved2);
+                });
+        }
     
-    max_relieved.to_string()
+        Ok(max_relieved.into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
-Valve BB has flow rate=13; tunnels lead to valves CC, AA
-Valve CC has flow rate=2; tunnels lead to valves DD, BB
-Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
-Valve EE has flow rate=3; tunnels lead to valves FF, DD
-Valve FF has flow rate=0; tunnels lead to valves EE, GG
-Valve GG has flow rate=0; tunnels lead to valves FF, HH
-Valve HH has flow rate=22; tunnel leads to valve GG
-Valve II has flow rate=0; tunnels lead to valves AA, JJ
-Valve JJ has flow rate=21; tunnel leads to valve II";
 
-    #[test]
-    fn part1_works() {
-        assert_eq!("1651", process_part1(INPUT));
-    }
+    aoc_core::examples!(Day16);
 
     #[test]
-    fn part2_works() {
-        assert_eq!("1707", process_part2(INPUT));
+    fn unknown_valves_are_errors() {
+        let err = Day16::parse("Valve AA has flow rate=0; tunnel leads to valve BB").unwrap_err();
+        assert_eq!("invalid input: line 1, column 49: expected the name of a valve in the list, found \"BB\"", err.to_string());
+        let err = Day16::parse("Valve BB has flow rate=0; tunnel leads to valve BB").unwrap_err();
+        assert_eq!("invalid input: line 1, column 51: expected a valve named AA, found end of input", err.to_string());
     }
 }
 
@@ -1,8 +1,10 @@
 use std::{iter::repeat, fmt::Display};
 use itertools::Itertools;
 
+use aoc_core::{Answer, Param, Params, Part, Result, Solution, Variant, parse_all, tracing::trace};
 use nom::{IResult, character::complete::char, multi::many1, branch::alt, Parser};
 
+// char based implementation, superseded by Game2 and kept as the `char_board` variant
 const WIDTH: i32 = 7;
 type Row = [char; WIDTH as usize];
 
@@ -101,8 +103,8 @@ impl Game {
             let jet = jets.next().unwrap();
             let shape_start_row = shape_end_row - height;
             // (left to right, bottom..up)
-            let s: Vec<_> = (0..WIDTH).into_iter()
-                .cartesian_product((shape_start_row..shape_end_row).into_iter())
+            let s: Vec<_> = (0..WIDTH)
+                .cartesian_product(shape_start_row..shape_end_row)
                 .filter(|(x, y)| self.board[*y][*x as usize] == '@')
                 .collect();
 
@@ -136,8 +138,8 @@ impl Game {
                 },
             };
 
-            let s: Vec<_> = (0..WIDTH).into_iter()
-                .cartesian_product((shape_start_row..shape_end_row).into_iter())
+            let s: Vec<_> = (0..WIDTH)
+                .cartesian_product(shape_start_row..shape_end_row)
                 .filter(|(x, y)| self.board[*y][*x as usize] == '@')
                 .collect();
             let movable = s.iter()
@@ -160,7 +162,7 @@ impl Game {
     }
 }
 
-enum Jet {
+pub enum Jet {
     Left,
     Right,
 }
@@ -188,10 +190,9 @@ fn jets(input: &str) -> IResult<&str, Vec<Jet>> {
     Ok((input, jets))
 }
 
-fn tower_height(input: &str, iterations: i64) -> String {
-    let (_input, jets) = jets(input).unwrap();
+fn tower_height(jets: &[Jet], iterations: i64) -> usize {
     let mut game = Game::new();
-    let mut jet_iter = repeat(&jets)
+    let mut jet_iter = repeat(jets)
         .flat_map(|jets| jets.iter());
     let shapes = init_shapes();
     let mut shape_iter = repeat(shapes.iter())
@@ -205,41 +206,76 @@ fn tower_height(input: &str, iterations: i64) -> String {
         .take_while(|r| r.iter().all(|c| c == &' '))
         .count();
 
-    // println!("{}", &game);
-    (game.board.len() - empty_rows_on_top - 1).to_string()
+    trace!("tower after {iterations} rocks:\n{game}");
+    game.board.len() - empty_rows_on_top - 1
 }
 
-fn tower_height2(input: &str, iterations: i64) -> String {
-    let (_input, jets) = jets(input).unwrap();
-    let mut game2 = Game2::new();
-    let mut jet_iter = repeat(&jets)
-        .flat_map(|jets| jets.iter());
-    let shapes = init_shapes2();
-    let mut shape_iter = repeat(shapes.iter())
-        .flatten();
-    for _i in 0..iterations {
-        game2.fall_shape(shape_iter.next().unwrap(), &mut jet_iter);
-        game2.ensure_top_rows();
+/// Rows at the top of the tower that go into the key of a state, deeper ones are taken to be
+/// out of reach for falling rocks.
+const SURFACE_ROWS: usize = 32;
+
+/// A tower with the position in the rock and jet patterns it got to.
+struct Tower<'a> {
+    game: Game2,
+    shapes: Vec<Shape2>,
+    jets: &'a [Jet],
+    rocks: usize,
+    jet: usize,
+}
+
+impl<'a> Tower<'a> {
+    fn new(jets: &'a [Jet]) -> Self {
+        Tower {
+            game: Game2::new(),
+            shapes: init_shapes2(),
+            jets,
+            rocks: 0,
+            jet: 0,
+        }
     }
 
-    let empty_rows_on_top = game2.board.iter().rev()
-        .take_while(|&&r| r == 0)
-        .count();
+    fn drop_rock(&mut self) {
+        let shape = &self.shapes[self.rocks % self.shapes.len()];
+        self.game.fall_shape(shape, self.jets, &mut self.jet);
+        self.game.ensure_top_rows();
+        self.rocks += 1;
+    }
+
+    fn height(&self) -> i64 {
+        let empty_rows_on_top = self.game.board.iter().rev()
+            .take_while(|&&r| r == 0)
+            .count();
+        (self.game.board.len() - empty_rows_on_top - 1) as i64 + self.game.height_offset
+    }
 
-    // println!("{}", &game2);
-    ((game2.board.len() - empty_rows_on_top - 1) as i64 + game2.height_offset).to_string()
+    /// Towers with the same key grow the same way from here on.
+    fn key(&self) -> (usize, usize, Vec<Row2>) {
+        let board = &self.game.board;
+        let surface = board[board.len().saturating_sub(SURFACE_ROWS)..].to_vec();
+        (self.rocks % self.shapes.len(), self.jet, surface)
+    }
 }
 
+fn tower_height2(jets: &[Jet], iterations: usize) -> i64 {
+    let mut tower = Tower::new(jets);
+    for _i in 0..iterations {
+        tower.drop_rock();
+    }
+
+    trace!("tower after {iterations} rocks:\n{}", tower.game);
+    tower.height()
+}
 
-pub fn process_part1(input: &str) -> String {
-    tower_height2(input, 2022i64)
+/// Like `tower_height2`, but only drops rocks until the tower starts repeating itself.
+fn tower_height_cycling(jets: &[Jet], iterations: usize) -> i64 {
+    cycle::extrapolate(Tower::new(jets), iterations, Tower::drop_rock, Tower::key, Tower::height)
 }
 
+
 type Row2 = u8;
 
 struct Game2 {
     board: Vec<Row2>,
-    #[allow(unused)] // todo: for optimizing out unreachable bottom part
     height_offset: i64,
 }
 
@@ -325,20 +361,22 @@ impl Game2 {
         if self.board[row] == 0b1111111 {
             self.board.drain(0..row);
             self.height_offset += row as i64;
-            // dbg!(row, self.height_offset);
+            trace!(row, height_offset = self.height_offset, "dropped the rows below a full one");
         }
         // if there is a row with all 1s, remove it and add a row on top
     }
 
-    fn fall_shape(&mut self, shape: &Shape2, jets: &mut dyn Iterator<Item = &Jet>) {
+    /// Drops `shape` starting with the jet at `jet`, which is left at the next jet to blow.
+    fn fall_shape(&mut self, shape: &Shape2, jets: &[Jet], jet: &mut usize) {
         let mut shape = shape.clone();
         // shape.iter().for_each(|_| self.add_row()); // todo: this sucks, will always be empty after coming to rest
 
         let mut shape_end_row = self.board.len();
         loop {
-            let jet = jets.next().unwrap();
+            let current = &jets[*jet];
+            *jet = (*jet + 1) % jets.len();
 
-            match jet {
+            match current {
                 Jet::Left => {
                     let can_shift_left = shape.iter()
                             .all(|row| row & 0b1000000 == 0);
@@ -376,25 +414,41 @@ impl Game2 {
     }
 }
 
-pub fn process_part2(input: &str) -> String {
-    tower_height2(input, 1_000_000_000_000i64)
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input<'a> = Vec<Jet>;
+
+    const PARAMS: &'static [Param] = &[
+        Param { name: "part1_rocks", default: "2022", about: "rocks dropped in part 1" },
+        Param { name: "part2_rocks", default: "1000000000000", about: "rocks dropped in part 2" },
+    ];
+
+    const VARIANTS: &'static [Variant] = &[
+        Variant {
+            name: "char_board",
+            part: Part::One,
+            solve: |input, params| Ok(tower_height(&Self::parse(input)?, params.get("part1_rocks")?).into()),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let jets = parse_all(input, jets)?;
+        Ok(jets)
+    }
+
+    fn part1(jets: &Self::Input<'_>, params: &Params) -> Result<Answer> {
+        Ok(tower_height2(jets, params.get("part1_rocks")?).into())
+    }
+
+    fn part2(jets: &Self::Input<'_>, params: &Params) -> Result<Answer> {
+        Ok(tower_height_cycling(jets, params.get("part2_rocks")?).into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
-
-    #[test]
-    fn part1_works() {
-        // let iterations = 2022;
-        // assert_eq!(tower_height(INPUT, iterations), tower_height2(INPUT, iterations));
-        assert_eq!("3068", process_part1(INPUT));
-    }
 
-    #[test]
-    #[ignore = "waaay too large number of iterations, will go OOM"]
-    fn part2_works() {
-        assert_eq!("1514285714288", process_part2(INPUT));
-    }
+    aoc_core::examples!(Day17);
 }
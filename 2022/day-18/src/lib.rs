@@ -1,144 +1,81 @@
-use std::collections::{BTreeSet, VecDeque};
+use std::collections::{BTreeSet, HashSet};
 
-use nom::{IResult, multi::separated_list1, character::complete::{self, line_ending}};
+use aoc_core::{Answer, Params, Result, Solution, parse_all};
+use geom::{Bounds, Point3};
+use aoc_parse::{lines_of, number, separated_triple};
+use nom::{IResult, character::complete::char};
+use search::flood_fill;
 
-type Point = (i32, i32, i32);
+type Point = Point3<i32>;
 
 fn point(input: &str) -> IResult<&str, Point> {
-    let (input, coords) = separated_list1(nom::character::complete::char(','), complete::i32)(input)?;
-    let p = (coords[0], coords[1], coords[2]);
-    Ok((input, p))
+    let (input, coords) = separated_triple(number, char(','))(input)?;
+    Ok((input, coords.into()))
 }
 
 fn points(input: &str) -> IResult<&str, Vec<Point>> {
-    let (input, points) = separated_list1(line_ending, point)(input)?;
+    let (input, points) = lines_of(point)(input)?;
     Ok((input, points))
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_input, points) = points(input).unwrap();
-    let droplet: BTreeSet<Point> = points.iter()
-        .cloned()
-        .collect();
+fn surround_with_lava(droplet: &BTreeSet<Point>) -> HashSet<Point> {
+    let bounds = Bounds::of(droplet.iter().copied())
+        .unwrap()
+        .grow(1);
 
-    points.iter()
-        .copied()
-        .map(|(x, y, z)| {
-            let adj = [
-                (x - 1, y, z),
-                (x + 1, y, z),
-                (x, y - 1, z),
-                (x, y + 1, z),
-                (x, y, z - 1),
-                (x, y, z + 1),
-            ];
-            
-            let covered = adj.iter()
-                .filter(|p| droplet.contains(p))
-                .count();
-            6 - covered
-        })
-        .sum::<usize>()
-        .to_string()
+    flood_fill([bounds.min], |p| p.neighbors6()
+        .filter(|&neighbor| bounds.contains(neighbor))
+        .filter(|neighbor| !droplet.contains(neighbor))
+    )
 }
 
-fn surround_with_lava(droplet: &BTreeSet<Point>) -> BTreeSet<Point> {
-    let (x_min, y_min, z_min) = droplet.iter()
-        .copied()
-        .reduce(|(x, y, z), (px, py, pz)| (x.min(px), y.min(py), z.min(pz)))
-        .map(|(x, y, z)| (x - 1, y - 1, z - 1))
-        .unwrap();
+pub struct Day18;
 
-    let (x_max, y_max, z_max) = droplet.iter()
-        .copied()
-        .reduce(|(x, y, z), (px, py, pz)| (x.max(px), y.max(py), z.max(pz)))
-        .map(|(x, y, z)| (x + 1, y + 1, z + 1))
-        .unwrap();
-    let mut res = BTreeSet::new();
+impl Solution for Day18 {
+    type Input<'a> = Vec<Point>;
 
-    // BFS
-    let mut q: VecDeque<Point> = VecDeque::new();
-    q.push_back((x_min, y_min, z_min));
-    while let Some(p) = q.pop_front() {
-        if res.contains(&p) {
-            continue;
-        }
-        let (x, y, z) = p;
-        res.insert((x, y, z));
-        [
-            (x - 1, y, z),
-            (x + 1, y, z),
-            (x, y - 1, z),
-            (x, y + 1, z),
-            (x, y, z - 1),
-            (x, y, z + 1),
-        ]
-            .iter()
-            .filter(|(x, y, z)| {
-                *x >= x_min && *x <= x_max &&
-                *y >= y_min && *y <= y_max &&
-                *z >= z_min && *z <= z_max
-            })
-            .filter(|neighbor| !droplet.contains(neighbor))
-            .for_each(|neighbor| q.push_back(*neighbor));
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let points = parse_all(input, points)?;
+        Ok(points)
     }
 
-    res
-}
+    fn part1(points: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let droplet: BTreeSet<Point> = points.iter()
+            .cloned()
+            .collect();
+
+        Ok(points.iter()
+            .copied()
+            .map(|p| {
+                let covered = p.neighbors6()
+                    .filter(|p| droplet.contains(p))
+                    .count();
+                6 - covered
+            })
+            .sum::<usize>()
+            .into())
+    }
 
-pub fn process_part2(input: &str) -> String {
-    let (_input, points) = points(input).unwrap();
-    let droplet: BTreeSet<Point> = points.iter()
-        .cloned()
-        .collect();
+    fn part2(points: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let droplet: BTreeSet<Point> = points.iter()
+            .cloned()
+            .collect();
     
-    let lava: BTreeSet<Point> = surround_with_lava(&droplet);
-
-    points.iter()
-        .copied()
-        .map(|(x, y, z)| {
-            let adj = [
-                (x - 1, y, z),
-                (x + 1, y, z),
-                (x, y - 1, z),
-                (x, y + 1, z),
-                (x, y, z - 1),
-                (x, y, z + 1),
-            ];
+        let lava: HashSet<Point> = surround_with_lava(&droplet);
 
-            let exposed = adj.iter()
+        Ok(points.iter()
+            .copied()
+            .map(|p| p.neighbors6()
                 .filter(|p| lava.contains(p))
-                .count();
-            exposed
-        })
-        .sum::<usize>()
-        .to_string()
+                .count())
+            .sum::<usize>()
+            .into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "2,2,2
-1,2,2
-3,2,2
-2,1,2
-2,3,2
-2,2,1
-2,2,3
-2,2,4
-2,2,6
-1,2,5
-3,2,5
-2,1,5
-2,3,5";
-
-    #[test]
-    fn part1_works() {
-        assert_eq!("64", process_part1(INPUT));
-    }
 
-    #[test]
-    fn part2_works() {
-        assert_eq!("58", process_part2(INPUT));
-    }
+    aoc_core::examples!(Day18);
 }
@@ -1,6 +1,10 @@
 use std::ops::{Add, Mul};
 
-use nom::{IResult, sequence::{preceded, terminated, delimited}, bytes::complete::tag, character::complete::{multispace0, self, line_ending}, multi::separated_list1 };
+use aoc_core::{Answer, Params, Result, Solution, parse_all_verbose};
+use aoc_parse::{blocks, lines_of};
+use nom::{sequence::{preceded, delimited, pair}, bytes::complete::tag, character::complete::{multispace0, self}, error::{context, VerboseError}};
+
+type IResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
 
 // implementation heavily influenced by: https://github.com/Crazytieguy/advent-of-code/blob/master/2022/src/bin/day19/main.rs
 
@@ -26,7 +30,7 @@ impl Resources {
 type Cost = Resources;
 
 #[derive(Debug)]
-struct Blueprint {
+pub struct Blueprint {
     id: usize,
     ore_cost: Cost,
     clay_cost: Cost,
@@ -167,38 +171,37 @@ impl State {
 }
 
 
-fn parse_usize(input: &str) -> IResult<&str, usize> {
+fn parse_usize(input: &str) -> IResult<'_, usize> {
     let (input, u) = complete::u32(input)?;
     Ok((input, u as usize))
 }
 
-fn obsidian_cost(input: &str) -> IResult<&str, Resources> {
-    let (input, ore) = preceded(multispace0,
-        delimited(tag("Each obsidian robot costs "), parse_usize, tag(" ore "))
-    )(input)?;
-    let (input, clay) = delimited(tag("and "), parse_usize, tag(" clay."))(input)?;
+fn obsidian_cost(input: &str) -> IResult<'_, Resources> {
+    let (input, (ore, clay)) = context("\"Each obsidian robot costs <n> ore and <n> clay.\"", preceded(multispace0, pair(
+        delimited(tag("Each obsidian robot costs "), parse_usize, tag(" ore ")),
+        delimited(tag("and "), parse_usize, tag(" clay.")),
+    )))(input)?;
     Ok((input, ONE_ORE * ore + ONE_CLAY * clay))
 }
 
-fn geode_cost(input: &str) -> IResult<&str, Resources> {
-    let (input, ore) = preceded(multispace0,
-        delimited(tag("Each geode robot costs "), parse_usize, tag(" ore "))
-    )(input)?;
-    let (input, obsidian) = delimited(tag("and "), parse_usize, tag(" obsidian."))(input)?;
+fn geode_cost(input: &str) -> IResult<'_, Resources> {
+    let (input, (ore, obsidian)) = context("\"Each geode robot costs <n> ore and <n> obsidian.\"", preceded(multispace0, pair(
+        delimited(tag("Each geode robot costs "), parse_usize, tag(" ore ")),
+        delimited(tag("and "), parse_usize, tag(" obsidian.")),
+    )))(input)?;
     Ok((input, ONE_ORE * ore + ONE_OBSIDIAN * obsidian))
 }
 
-fn blueprint(input: &str) -> IResult<&str, Blueprint> {
-    let (input, _) = preceded(multispace0, tag("Blueprint "))(input)?;
-    let (input, id) = terminated(parse_usize, tag(":"))(input)?;
-    let (input, ore) = delimited(
+fn blueprint(input: &str) -> IResult<'_, Blueprint> {
+    let (input, id) = context("\"Blueprint <n>:\"", delimited(tag("Blueprint "), parse_usize, tag(":")))(input)?;
+    let (input, ore) = context("\"Each ore robot costs <n> ore.\"", delimited(
             preceded(multispace0, tag("Each ore robot costs ")),
             parse_usize,
-        tag(" ore."))(input)?;
-    let (input, clay) = delimited(
+        tag(" ore.")))(input)?;
+    let (input, clay) = context("\"Each clay robot costs <n> ore.\"", delimited(
             preceded(multispace0, tag("Each clay robot costs ")),
             parse_usize,
-        tag(" ore."))(input)?;
+        tag(" ore.")))(input)?;
     let (input, obsidian) = obsidian_cost(input)?;
     let (input, geode) = geode_cost(input)?;
 
@@ -238,45 +241,45 @@ fn blueprint_max_geodes(blueprint: &Blueprint, time: i32) -> i32 {
     best
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_input, blueprints) = separated_list1(line_ending, blueprint)(input).unwrap();
-    blueprints.iter()
-        .map(|blueprint| blueprint_max_geodes(blueprint, 24) * blueprint.id as i32)
-        .sum::<i32>()
-        .to_string()
-}
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input<'a> = Vec<Blueprint>;
 
-pub fn process_part2(input: &str) -> String {
-    let (_input, blueprints) = separated_list1(line_ending, blueprint)(input).unwrap();
-    blueprints.iter()
-        .take(3)
-        .map(|blueprint| blueprint_max_geodes(blueprint, 32))
-        .product::<i32>()
-        .to_string()
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        // the example has a blank line after every blueprint, the real input one per line
+        let blueprints = parse_all_verbose(input, blocks(lines_of(blueprint)))?;
+        Ok(blueprints.into_iter().flatten().collect())
+    }
+
+    fn part1(blueprints: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(blueprints.iter()
+            .map(|blueprint| blueprint_max_geodes(blueprint, 24) * blueprint.id as i32)
+            .sum::<i32>()
+            .into())
+    }
+
+    fn part2(blueprints: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(blueprints.iter()
+            .take(3)
+            .map(|blueprint| blueprint_max_geodes(blueprint, 32))
+            .product::<i32>()
+            .into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "Blueprint 1:
-  Each ore robot costs 4 ore.
-  Each clay robot costs 2 ore.
-  Each obsidian robot costs 3 ore and 14 clay.
-  Each geode robot costs 2 ore and 7 obsidian.
-
-Blueprint 2:
-  Each ore robot costs 2 ore.
-  Each clay robot costs 3 ore.
-  Each obsidian robot costs 3 ore and 8 clay.
-  Each geode robot costs 3 ore and 12 obsidian.";
 
-    #[test]
-    fn part1_works() {
-        assert_eq!("33", process_part1(INPUT));
-    }
+    aoc_core::examples!(Day19);
 
     #[test]
-    fn part2_works() {
-        assert_eq!("3472", process_part2(INPUT));
+    fn parse_reports_what_was_expected() {
+        let blueprint = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.";
+        assert_eq!(2, Day19::parse(&format!("{blueprint}\n{blueprint}")).unwrap().len());
+
+        let err = Day19::parse(&format!("{blueprint}\n{}", blueprint.replace("7 obsidian", "7 clay"))).unwrap_err();
+        assert_eq!("invalid input: line 2, column 150: expected \"Each geode robot costs <n> ore and <n> obsidian.\", found \" clay.\"", err.to_string());
     }
 }
@@ -1,3 +1,5 @@
+use aoc_core::{Answer, Error, Param, Params, ParseError, Result, Solution, tracing::debug};
+
 // this can be done easier/faster with just .remove(pos) ;)
 fn shift_pos(v: &mut [(usize, i64)], pos: usize, size: i64) {
     let (_, num) = v[pos];
@@ -30,15 +32,15 @@ fn shift_pos(v: &mut [(usize, i64)], pos: usize, size: i64) {
 
 }
 
-fn get_coords_output(v: &[(usize, i64)]) -> String {
+fn get_coords_output(v: &[(usize, i64)]) -> Result<i64> {
     let size = v.len();
-    let pos0 = v.iter().position(|(_, num)| *num == 0).unwrap();
+    let pos0 = v.iter().position(|(_, num)| *num == 0)
+        .ok_or_else(|| Error::NoSolution("there is no 0 to count from".to_string()))?;
 
-    [1000, 2000, 3000].iter()
+    Ok([1000, 2000, 3000].iter()
         .map(|coord_pos| v[(coord_pos + pos0) % size].1)
-        .inspect(|num| println!("coord: {}", num))
-        .sum::<i64>()
-        .to_string()
+        .inspect(|num| debug!(num, "grove coordinate"))
+        .sum::<i64>())
 }
 
 fn mix(v: &mut [(usize, i64)]) {
@@ -49,55 +51,70 @@ fn mix(v: &mut [(usize, i64)]) {
     }
 }
 
-// expected answer for part1: 7713, actual 15744
-pub fn process_part1(input: &str) -> String {
-    let nums = input.split('\n')
-        .filter_map(|l| l.parse::<i64>().ok())
-        .collect::<Vec<i64>>();
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input<'a> = Vec<i64>;
+
+    const PARAMS: &'static [Param] = &[
+        Param { name: "key", default: "811589153", about: "decryption key the numbers are multiplied with in part 2" },
+        Param { name: "rounds", default: "10", about: "how often the numbers are mixed in part 2" },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let nums = input.lines()
+            .map(|l| l.parse::<i64>().map_err(|_| ParseError::at(input, l, "a number")))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // a single number has nowhere to move, and the coordinates are counted from the 0
+        let end_of_input = &input[input.len()..];
+        if nums.len() < 2 {
+            return Err(ParseError::at(input, end_of_input, "at least two numbers").into());
+        }
+        if !nums.contains(&0) {
+            return Err(ParseError::at(input, end_of_input, "a 0 among the numbers").into());
+        }
+        Ok(nums)
+    }
 
-    let mut v = nums.iter()
-        .copied()
-        .enumerate()
-        .collect::<Vec<(usize, i64)>>();
+    fn part1(nums: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let mut v = nums.iter()
+            .copied()
+            .enumerate()
+            .collect::<Vec<(usize, i64)>>();
 
-    mix(&mut v);
+        mix(&mut v);
 
-    get_coords_output(&v)
-}
+        Ok(get_coords_output(&v)?.into())
+    }
 
-// expected answer for part2: 1664569352803
-pub fn process_part2(input: &str) -> String {
-    const ENCRYPTION_KEY: i64 = 811589153;
-    let nums = input.split('\n')
-        .filter_map(|l| l.parse::<i64>().ok())
-        .map(|num| num * ENCRYPTION_KEY)
-        .collect::<Vec<i64>>();
-
-    let mut v = nums.iter()
-        .copied()
-        .enumerate()
-        .collect::<Vec<(usize, i64)>>();
-    for _ in 0..10 {
-        mix(&mut v);
+    fn part2(nums: &Self::Input<'_>, params: &Params) -> Result<Answer> {
+        let key: i64 = params.get("key")?;
+        let rounds: usize = params.get("rounds")?;
+
+        let mut v = nums.iter()
+            .map(|num| num * key)
+            .enumerate()
+            .collect::<Vec<(usize, i64)>>();
+        for _ in 0..rounds {
+            mix(&mut v);
+        }
+        Ok(get_coords_output(&v)?.into())
     }
-    get_coords_output(&v)
 }
 
 #[cfg(test)]
 mod tests {
-
     use super::*;
-    const INPUT: &str = "1
-2
--3
-3
--2
-0
-4";
+
+    aoc_core::examples!(Day20);
 
     #[test]
-    fn part1_works() {
-        assert_eq!("3", process_part1(INPUT));
+    fn unmixable_inputs_are_errors() {
+        let err = Day20::parse("0").unwrap_err();
+        assert_eq!("invalid input: line 1, column 2: expected at least two numbers, found end of input", err.to_string());
+        let err = Day20::parse("1\n2").unwrap_err();
+        assert_eq!("invalid input: line 2, column 2: expected a 0 among the numbers, found end of input", err.to_string());
     }
 
     fn prepare_test_vec<const N: usize>(v: &[i64; N]) -> Vec<(usize, i64)> {
@@ -143,9 +160,4 @@ mod tests {
         let result = v.iter().map(|(_p, n)| *n).collect::<Vec<_>>();
         assert_eq!(vec![2, -2, 3, 4], result);
     }
-
-    #[test]
-    fn part2_works() {
-        assert_eq!("1623178306", process_part2(INPUT));
-    }
 }
@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
+use aoc_core::{Answer, Error, ParseError, Params, Result, Solution, parse_all};
 use nom::{IResult, sequence::separated_pair, character::complete::{alpha1, self, line_ending}, bytes::streaming::tag, branch::alt, Parser, multi::separated_list1};
 
 #[derive(Debug)]
-enum Op {
+pub enum Op {
     Add,
     Div,
     Mul,
@@ -11,18 +12,18 @@ enum Op {
 }
 
 #[derive(Debug)]
-enum Job<'a> {
+pub enum Job<'a> {
     Yell(i64),
     Operation(&'a str, Op, &'a str),
 }
 
 #[derive(Debug)]
-struct Monkey<'a> {
+pub struct Monkey<'a> {
     id: &'a str,
     job: Job<'a>,
 }
 
-fn job(input: &str) -> IResult<&str, Job> {
+fn job(input: &str) -> IResult<&str, Job<'_>> {
     let (input, job) = alt((
         alt((
             separated_pair(alpha1, tag(" + "), alpha1).map(|(a, b)| Job::Operation(a, Op::Add, b)),
@@ -36,7 +37,7 @@ fn job(input: &str) -> IResult<&str, Job> {
     Ok((input, job))
 }
 
-fn monkey(input: &str) -> IResult<&str, Monkey> {
+fn monkey(input: &str) -> IResult<&str, Monkey<'_>> {
     let (input, (id, job)) = separated_pair(
         alpha1,
         tag(": "),
@@ -50,8 +51,8 @@ fn what_monkey_yells(m: &Monkey, monkeys: &HashMap<&str, Monkey>) -> i64 {
     match &m.job {
         Job::Yell(num) => *num,
         Job::Operation(a, op, b) => {
-            let a = what_monkey_yells(monkeys.get(a).unwrap(), monkeys);
-            let b = what_monkey_yells(monkeys.get(b).unwrap(), monkeys);
+            let a = what_monkey_yells(&monkeys[a], monkeys);
+            let b = what_monkey_yells(&monkeys[b], monkeys);
             match op {
                 Op::Add => a + b,
                 Op::Div => a / b,
@@ -62,18 +63,6 @@ fn what_monkey_yells(m: &Monkey, monkeys: &HashMap<&str, Monkey>) -> i64 {
     }
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_input, monkeys) = separated_list1(line_ending, monkey)(input).unwrap();
-    let monkeys = HashMap::<&str, Monkey>::from_iter(
-        monkeys.into_iter().map(|m| (m.id, m))
-    );
-
-    let root = monkeys.get("root").unwrap();
-
-    what_monkey_yells(root, &monkeys)
-        .to_string()
-}
-
 #[derive(Debug)]
 enum Dir {
     Left,
@@ -88,12 +77,12 @@ fn find_monkey_path(id: &str, m: &Monkey, monkeys: &HashMap<&str, Monkey>) -> Op
     match m.job {
         Job::Yell(_) => None,
         Job::Operation(left, _, right) => {
-            if let Some(mut v) = find_monkey_path(id, monkeys.get(left).unwrap(), monkeys) {
+            if let Some(mut v) = find_monkey_path(id, &monkeys[left], monkeys) {
                 v.push(Dir::Left);
                 return Some(v);
             }
 
-            if let Some(mut v) = find_monkey_path(id, monkeys.get(right).unwrap(), monkeys) {
+            if let Some(mut v) = find_monkey_path(id, &monkeys[right], monkeys) {
                 v.push(Dir::Right);
                 return Some(v);
             }
@@ -109,10 +98,9 @@ fn what_monkey_needs_to_yell(m: &Monkey, path: &[Dir], wanted: i64, monkeys: &Ha
 
     let search_dir = &path[0];
     let new_path = &path[1..];
-    let m = monkeys.get(m.id).unwrap();
     if let Job::Operation(left, op, right) = &m.job {
-        let right = monkeys.get(right).unwrap();
-        let left = monkeys.get(left).unwrap();
+        let right = &monkeys[right];
+        let left = &monkeys[left];
 
         match search_dir {
             Dir::Left => {
@@ -142,60 +130,74 @@ fn what_monkey_needs_to_yell(m: &Monkey, path: &[Dir], wanted: i64, monkeys: &Ha
     }
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (_input, monkeys) = separated_list1(line_ending, monkey)(input).unwrap();
-    let monkeys = HashMap::<&str, Monkey>::from_iter(
-        monkeys.into_iter().map(|m| (m.id, m))
-    );
-
-    let root = monkeys.get("root").unwrap();
-    if let Job::Operation(left, _, right) = root.job {
-        let mut path = find_monkey_path("humn", root, &monkeys).unwrap();
-        let right = monkeys.get(right).unwrap();
-        let left = monkeys.get(left).unwrap();
-        path.reverse();
-        let p0 = &path[0];
-        match p0 {
-            Dir::Left => {
-                let wanted = what_monkey_yells(right, &monkeys);
-                what_monkey_needs_to_yell(left, &path[1..], wanted, &monkeys)
-            }
-            Dir::Right => {
-                let wanted = what_monkey_yells(left, &monkeys);
-                what_monkey_needs_to_yell(right, &path[1..], wanted, &monkeys)
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input<'a> = HashMap<&'a str, Monkey<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let monkeys = parse_all(input, separated_list1(line_ending, monkey))?;
+        let monkeys = HashMap::<&str, Monkey>::from_iter(
+            monkeys.into_iter().map(|m| (m.id, m))
+        );
+
+        // every monkey an operation waits for has to be there, so the lookups below can't fail
+        for m in monkeys.values() {
+            if let Job::Operation(left, _, right) = m.job {
+                if let Some(missing) = [left, right].into_iter().find(|id| !monkeys.contains_key(id)) {
+                    return Err(ParseError::at(input, missing, "the name of a monkey in the list").into());
+                }
             }
-        }.to_string()
-    } else {
-        unreachable!() // unreachable with proper input
+        }
+        Ok(monkeys)
+    }
+
+    fn part1(monkeys: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let root = monkeys.get("root")
+            .ok_or_else(|| Error::NoSolution("there is no monkey named root".to_string()))?;
+
+        Ok(what_monkey_yells(root, monkeys)
+            .into())
+    }
+
+    fn part2(monkeys: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let root = monkeys.get("root")
+            .ok_or_else(|| Error::NoSolution("there is no monkey named root".to_string()))?;
+        if let Job::Operation(left, _, right) = root.job {
+            let mut path = find_monkey_path("humn", root, monkeys)
+                .ok_or_else(|| Error::NoSolution("humn is not below root".to_string()))?;
+            let right = &monkeys[right];
+            let left = &monkeys[left];
+            path.reverse();
+            let p0 = &path[0];
+            let yell = match p0 {
+                Dir::Left => {
+                    let wanted = what_monkey_yells(right, monkeys);
+                    what_monkey_needs_to_yell(left, &path[1..], wanted, monkeys)
+                }
+                Dir::Right => {
+                    let wanted = what_monkey_yells(left, monkeys);
+                    what_monkey_needs_to_yell(right, &path[1..], wanted, monkeys)
+                }
+            };
+            Ok(yell.into())
+        } else {
+            Err(Error::NoSolution("root does not compare two monkeys".to_string()))
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "root: pppw + sjmn
-dbpl: 5
-cczh: sllz + lgvd
-zczc: 2
-ptdq: humn - dvpt
-dvpt: 3
-lfqf: 4
-humn: 5
-ljgn: 2
-sjmn: drzm * dbpl
-sllz: 4
-pppw: cczh / lfqf
-lgvd: ljgn * ptdq
-drzm: hmdt - zczc
-hmdt: 32";
 
-    #[test]
-    fn part1_works() {
-        assert_eq!("152", process_part1(INPUT));
-    }
+    aoc_core::examples!(Day21);
 
     #[test]
-    fn part2_works() {
-        assert_eq!("301", process_part2(INPUT));
+    fn unknown_monkeys_are_errors() {
+        let Err(Error::Parse(err)) = Day21::parse("root: abcd + efgh\nabcd: 3") else { panic!("efgh is missing") };
+        assert_eq!((1, 14), (err.line, err.column));
+        let monkeys = Day21::parse("abcd: 3").unwrap();
+        assert!(matches!(Day21::part1(&monkeys, &Params::default()), Err(Error::NoSolution(_))));
     }
 }
@@ -1,16 +1,18 @@
 use std::{collections::BTreeSet};
 
-use nom::{IResult, character::complete::{space0, one_of, line_ending, self}, multi::{many1, separated_list1}, sequence::preceded, branch::alt, Parser};
+use aoc_core::{Answer, Error, Params, Result, Solution, parse_all, tracing::debug};
+use aoc_parse::{blank_lines, lines_of};
+use nom::{IResult, character::complete::{space0, one_of, self}, multi::many1, sequence::{pair, terminated}, branch::alt, Parser};
 
 #[derive(Debug)]
-struct Row {
+pub struct Row {
     start: u16,
     end: u16,
     walls: BTreeSet<u16>,
 }
 
 #[derive(Debug)]
-enum Movement {
+pub enum Movement {
     Go(u16),
     Left,
     Right,
@@ -40,12 +42,10 @@ fn row(input: &str) -> IResult<&str, Row> {
 }
 
 fn parse_input(input: &str) -> IResult<&str, (Vec<Row>, Vec<Movement>)> { // todo
-    let (input, rows) = separated_list1(line_ending, row)(input)?;
-    let (input, _) = preceded(space0, line_ending)(input)?;
-    let (input, _) = line_ending(input)?;
+    let (input, rows) = terminated(lines_of(row), pair(space0, blank_lines))(input)?;
     let (input, movements) = many1(alt(
         (
-            complete::u16.map(|n| Movement::Go(n)),
+            complete::u16.map(Movement::Go),
             one_of("LR").map(|c| if c == 'L' { Movement::Left } else { Movement::Right } ),
         )
     ))(input)?;
@@ -53,39 +53,41 @@ fn parse_input(input: &str) -> IResult<&str, (Vec<Row>, Vec<Movement>)> { // tod
     Ok((input, (rows, movements)))
 }
 
-fn prepare(input: &str) -> (Vec<Row>, Vec<Row>, Vec<Movement>, u16) {
-    let (_input, (rows, movements)) = parse_input(input).unwrap();
+/// Rows, columns, movements and the starting x position.
+pub type Board = (Vec<Row>, Vec<Row>, Vec<Movement>, u16);
+
+fn prepare(input: &str) -> Result<Board> {
+    let (rows, movements) = parse_all(input, parse_input)?;
     let column_number = rows.iter()
         .map(|row| row.end)
         .max()
-        .unwrap();
+        .unwrap_or(0);
     let row_number = rows.len();
-    let columns: Vec<Row> = (0..column_number).into_iter()
+    let columns = (0..column_number)
         .map(|index| {
             let start = rows.iter()
                 .position(|row| row.start <= index && row.end > index)
-                .unwrap();
+                .ok_or_else(|| Error::NoSolution(format!("column {index} has no tiles")))?;
             let end = rows[start..].iter()
                 .position(|row| index < row.start || index >= row.end)
                 .map(|pos| pos + start)
                 .unwrap_or(row_number);
-            let walls = (start..end).into_iter()
+            let walls = (start..end)
                 .flat_map(|y| rows[y].walls.get(&index).map(|_| y as u16))
                 .collect();
 
-            Row {
+            Ok(Row {
                 start: start as u16,
                 end: end as u16,
                 walls,
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<Row>>>()?;
 
-        let start_x = (rows[0].start..rows[0].end).into_iter()
-            .position(|x| !rows[0].walls.contains(&x))
-            .map(|x| x as u16 + rows[0].start)
-            .unwrap();
-        (rows, columns, movements, start_x)
+    let start_x = (rows[0].start..rows[0].end)
+        .find(|x| !rows[0].walls.contains(x))
+        .ok_or_else(|| Error::NoSolution("the top row is all walls".to_string()))?;
+    Ok((rows, columns, movements, start_x))
 }
 
 fn do_the_walk(row: &Row, pos: &u16, forward: bool, steps: &u16) -> u16 {
@@ -112,38 +114,6 @@ fn do_the_walk(row: &Row, pos: &u16, forward: bool, steps: &u16) -> u16 {
     pos
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (rows, columns, movements, x) = prepare(input);
-    let mut x = x;
-    let mut y = 0;
-    let mut heading = 0;
-
-    for movement in movements.iter() {
-        match movement {
-            Movement::Go(steps) => {
-                if heading % 2 == 0 {
-                    // moving left-right
-                    let row = &rows[y as usize];
-                    let forward = heading == 0;
-                    x = do_the_walk(row, &x, forward, steps);
-                } else {
-                    // moving up-down
-                    let row = &columns[x as usize];
-                    let forward = heading == 1;
-                    y = do_the_walk(row, &y, forward, steps);
-                }
-
-            },
-            Movement::Left => heading = (heading + 3) % 4,
-            Movement::Right => heading = (heading + 1) % 4,
-        }
-
-    }
-
-    dbg!(&x, &y, &heading);
-    (1000 * (y + 1) + 4 * (x + 1) + heading).to_string()
-}
-
 //         1111
 //         1111
 //         1111
@@ -174,36 +144,62 @@ pub fn process_part1(input: &str) -> String {
 //   - each transition is: (target face, translate_x fn, translate_y fn, new heading)
 //   - find out when crossing face boundary, and apply appropriate transition to 
 
-pub fn process_part2(input: &str) -> String {
-    input.into()
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input<'a> = Board;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        prepare(input)
+    }
+
+    fn part1(board: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let (rows, columns, movements, x) = board;
+        let mut x = *x;
+        let mut y = 0;
+        let mut heading = 0;
+
+        for movement in movements.iter() {
+            match movement {
+                Movement::Go(steps) => {
+                    if heading % 2 == 0 {
+                        // moving left-right
+                        let row = &rows[y as usize];
+                        let forward = heading == 0;
+                        x = do_the_walk(row, &x, forward, steps);
+                    } else {
+                        // moving up-down
+                        let row = &columns[x as usize];
+                        let forward = heading == 1;
+                        y = do_the_walk(row, &y, forward, steps);
+                    }
+
+                },
+                Movement::Left => heading = (heading + 3) % 4,
+                Movement::Right => heading = (heading + 1) % 4,
+            }
+
+        }
+
+        debug!(x, y, heading, "final position");
+        Ok((1000 * (y + 1) + 4 * (x + 1) + heading).into())
+    }
+
+    fn part2(_board: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        // walking around the cube is not implemented yet
+        Err(Error::Unimplemented)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "        ...#
-        .#..
-        #...
-        ....
-...#.......#
-........#...
-..#....#....
-..........#.
-        ...#....
-        .....#..
-        .#......
-        ......#.
-
-10R5L5R10L4R5L5";
 
-    #[test]
-    fn part1_works() {
-        assert_eq!("6032", process_part1(INPUT));
-    }
+    aoc_core::examples!(Day22);
 
     #[test]
-    #[ignore = "not implemented"]
-    fn part2_works() {
-        assert_eq!("5031", process_part2(INPUT));
+    fn impossible_boards_are_errors() {
+        assert!(matches!(prepare("##\n..\n\n1R"), Err(Error::NoSolution(_))));
+        assert!(matches!(prepare("..\n   .\n\n1R"), Err(Error::NoSolution(_))));
     }
 }
@@ -1,53 +1,36 @@
-use std::{collections::{BTreeSet, BTreeMap}, ops::Add};
+use std::collections::{BTreeSet, BTreeMap};
 
 use itertools::Itertools;
-
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Add<(i32, i32)> for Point {
-    type Output = Point;
-
-    fn add(self, rhs: (i32, i32)) -> Self::Output {
-        Point {
-            x: self.x + rhs.0,
-            y: self.y + rhs.1,
-        }
+use aoc_core::{Answer, Params, ParseError, Result, Solution, tracing::trace};
+use geom::{Bounds, Dir8, Point2};
+use grid::Grid;
+
+pub type Point = Point2<i32>;
+
+fn parse_input(input: &str) -> Result<BTreeSet<Point>> {
+    let map = Grid::parse(input, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    let elves: BTreeSet<Point> = map.find(|&elf| elf)
+        .map(|pos| Point::new(pos.x as i32, pos.y as i32))
+        .collect();
+    if elves.is_empty() {
+        return Err(ParseError::at(input, &input[input.len()..], "at least one elf '#'").into());
     }
+    Ok(elves)
 }
 
-fn parse_input(input: &str) -> BTreeSet<Point> {
-    input.lines()
-        .enumerate()
-        .flat_map(|(y, line)| line.chars()
-            .enumerate()
-            .filter_map(
-                move |(x, c)| if c == '#' {
-                    Some(Point {
-                        x: x as i32,
-                        y: y as i32 
-                    })
-                }
-                else {
-                    None 
-                }
-            )
-        )
-        .collect()
-}
-
-fn find_valid_move(elf: &Point, elves: &BTreeSet<Point>, check_directions: &[[(i32, i32); 3]; 4], round: usize) -> Option<Point> {
+fn find_valid_move(elf: &Point, elves: &BTreeSet<Point>, check_directions: &[[Dir8; 3]; 4], round: usize) -> Option<Point> {
     (0..4)
         .find_map(|idx| {
             let idx = (idx + round) % check_directions.len();
             let valid_move = check_directions[idx].into_iter()
-                .map(|offset| *elf + offset)
+                .map(|dir| *elf + dir.offset())
                 .all(|point| !elves.contains(&point));
             if valid_move {
-                let dst = *elf + check_directions[idx][0];
+                let dst = *elf + check_directions[idx][0].offset();
                 Some(dst)
             } else {
                 None
@@ -55,48 +38,34 @@ fn find_valid_move(elf: &Point, elves: &BTreeSet<Point>, check_directions: &[[(i
         })
 }
 
-fn bounding_box(elves: &BTreeSet<Point>) -> (i32, i32, i32, i32) {
-    elves.iter()
-        .fold(
-            (i32::MAX, i32::MAX, i32::MIN, i32::MIN),
-            |(x0, y0, x1, y1), Point {x, y}| (x0.min(*x), y0.min(*y), x1.max(*x), y1.max(*y))
-        )
+fn bounding_box(elves: &BTreeSet<Point>) -> Bounds<Point> {
+    Bounds::of(elves.iter().copied())
+        .expect("elves never disappear")
 }
 
 fn free_space(elves: &BTreeSet<Point>) -> i32 {
-    let (xmin, ymin, xmax, ymax) = bounding_box(elves);
-    let area = (xmax - xmin + 1) * (ymax - ymin + 1);
-    area - elves.len() as i32
+    bounding_box(elves).area() - elves.len() as i32
 }
 
-fn display_map(elves: &BTreeSet<Point>) {
-    let (xmin, ymin, xmax, ymax) = bounding_box(elves);
-    for y in ymin..=ymax {
-        for x in xmin..=xmax {
-            let c = if elves.contains(&Point {x, y}) {
-                '#'
-            } else {
-                '.'
-            };
-            print!("{}", c);
-        }
-        println!();
-    }
+fn display_map(elves: &BTreeSet<Point>) -> String {
+    let Bounds { min, max } = bounding_box(elves);
+    (min.y..=max.y)
+        .map(|y| (min.x..=max.x)
+            .map(|x| if elves.contains(&Point::new(x, y)) { '#' } else { '.' })
+            .collect::<String>())
+        .join("\n")
 }
 
-const CHECK_DIRECTIONS: [[(i32, i32); 3]; 4] = 
+const CHECK_DIRECTIONS: [[Dir8; 3]; 4] = 
     [
-        [(0, -1), (-1, -1), (1, -1)], // N, NE, NW
-        [(0, 1), (-1, 1), (1, 1)], // S, SE, SW
-        [(-1, 0), (-1, -1), (-1, 1)], // W, NW, SW
-        [(1, 0), (1, -1), (1, 1)], // E, NE, SE
+        [Dir8::N, Dir8::NW, Dir8::NE],
+        [Dir8::S, Dir8::SW, Dir8::SE],
+        [Dir8::W, Dir8::NW, Dir8::SW],
+        [Dir8::E, Dir8::NE, Dir8::SE],
     ];
 
 fn should_look_for_move(elf: &Point, elves: &BTreeSet<Point>) -> bool {
-    (-1..=1)
-        .cartesian_product(-1..=1)
-        .filter(|(x, y)| *x != 0 || *y != 0)
-        .map(|(x, y)| *elf + (x, y))
+    elf.neighbors8()
         .any(|point| elves.contains(&point))
 }
 
@@ -136,46 +105,47 @@ fn process_round(elves: &mut BTreeSet<Point>, round: usize) -> bool {
     no_elf_moved
 }
 
-pub fn process_part1(input: &str) -> String {
-    let mut elves = parse_input(input);
-    println!("==== Initial state:");
-    display_map(&elves);
-    for round in 0..10 {
-        process_round(&mut elves, round);
-        println!("==== Round {}:", round + 1);
-        display_map(&elves);
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input<'a> = BTreeSet<Point>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parse_input(input)
     }
 
-    free_space(&elves).to_string()
-}
+    fn part1(elves: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let mut elves = elves.clone();
+        trace!("initial state:\n{}", display_map(&elves));
+        for round in 0..10 {
+            process_round(&mut elves, round);
+            trace!("after round {}:\n{}", round + 1, display_map(&elves));
+        }
 
-pub fn process_part2(input: &str) -> String {
-    let mut elves = parse_input(input);
-    let mut round = 0;
-    while !process_round(&mut elves, round) {
-        round += 1;
+        Ok(free_space(&elves).into())
+    }
+
+    fn part2(elves: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let mut elves = elves.clone();
+        let mut round = 0;
+        while !process_round(&mut elves, round) {
+            round += 1;
+        }
+        Ok((round + 1).into())
     }
-    (round + 1).to_string()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "....#..
-..###.#
-#...#.#
-.#...##
-#.###..
-##.#.##
-.#..#..";
 
-    #[test]
-    fn part1_works() {
-        assert_eq!("110", process_part1(INPUT));
-    }
+    aoc_core::examples!(Day23);
 
     #[test]
-    fn part2_works() {
-        assert_eq!("20", process_part2(INPUT));
+    fn parse_rejects_other_characters_and_no_elves() {
+        let err = Day23::parse("#.\n.x").unwrap_err();
+        assert_eq!("invalid input: line 2, column 2: expected a grid cell, found \"x\"", err.to_string());
+        let err = Day23::parse("..\n..").unwrap_err();
+        assert_eq!("invalid input: line 2, column 3: expected at least one elf '#', found end of input", err.to_string());
     }
 }
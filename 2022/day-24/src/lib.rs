@@ -1,5 +1,4 @@
 // 0 -> empty
-// 1 -> elf
 // 2 -> <
 // 4 -> >
 // 8 -> ^
@@ -8,14 +7,13 @@
 // 64 -> entry
 // 128 -> exit
 
-use std::{fmt::Display, str::FromStr, iter::repeat};
+use std::{fmt::Display, str::FromStr, iter::once};
 
-use pathfinding::prelude::astar;
+use aoc_core::{Answer, Error, Params, ParseError, Result, Solution};
+use grid::{Grid, Pos};
+use search::astar;
 
-#[allow(unused)]
 const EMPTY: u8 = 0;
-#[allow(unused)]
-const ELF: u8 = 1;
 const LEFT: u8 = 2;
 const RIGHT: u8 = 4;
 const UP: u8 = 8;
@@ -26,21 +24,19 @@ const EXIT: u8 = 128;
 
 #[derive(Hash, Eq, PartialEq)]
 struct Map {
-    dx: usize,
-    dy: usize,
-    field: Vec<u8>,
+    field: Grid<u8>,
 }
 
 impl Map {
     fn left_from(&self, x: usize) -> usize {
         match x {
-            1 => self.dx - 2,
+            1 => self.field.width() - 2,
             _ => x - 1,
         }
     }
 
     fn right_from(&self, x: usize) -> usize {
-        if x == self.dx - 2 {
+        if x == self.field.width() - 2 {
             1
         } else {
             x + 1
@@ -49,206 +45,138 @@ impl Map {
 
     fn up_from(&self, y: usize) -> usize {
         match y {
-            1 => self.dy - 2,
+            1 => self.field.height() - 2,
             _ => y - 1,
         }
     }
 
     fn down_from(&self, y: usize) -> usize {
-        if y == self.dy - 2 {
+        if y == self.field.height() - 2 {
             1
         } else {
             y + 1
         }
     }
 
-    fn to_idx(&self, x: usize, y: usize) -> usize {
-        y * self.dx + x
-    }
-
     fn after_minute(&self) -> Self {
-        let mut field = Vec::with_capacity(self.field.len());
+        // retain only solid (walls, entrance, exit) objects
+        let mut field = self.field.map(|v| v & 0b11100000);
 
-        for i in 0..self.field.len() {
-            field.push(self.field[i] & 0b11100000); // retain only solid (walls, entrance, exit) objects
-        }
-        let mut res = Map {
-            dx: self.dx,
-            dy: self.dy,
-            field,
-        };
-
-        for y in 0..self.dy {
-            for x in 0..self.dx {
-                if let Some(v) = self.get_xy(x, y) {
-                    if v & LEFT != 0 {
-                        res.or_xy(self.left_from(x), y, LEFT);
-                    }
-                    if v & RIGHT != 0 {
-                        res.or_xy(self.right_from(x), y, RIGHT);
-                    }
-                    if v & UP != 0 {
-                        res.or_xy(x, self.up_from(y), UP);
-                    }
-                    if v & DOWN != 0 {
-                        res.or_xy(x, self.down_from(y), DOWN);
-                    }
-                };
+        for (Pos { x, y }, &v) in self.field.iter() {
+            if v & LEFT != 0 {
+                field[Pos::new(self.left_from(x), y)] |= LEFT;
+            }
+            if v & RIGHT != 0 {
+                field[Pos::new(self.right_from(x), y)] |= RIGHT;
+            }
+            if v & UP != 0 {
+                field[Pos::new(x, self.up_from(y))] |= UP;
+            }
+            if v & DOWN != 0 {
+                field[Pos::new(x, self.down_from(y))] |= DOWN;
             }
         }
-        res
-    }
-
-    fn get_xy(&self, x: usize, y: usize) -> Option<u8> {
-        self.field.get(self.to_idx(x, y)).copied()
-    }
-
-    fn or_xy(&mut self, x: usize, y: usize, bits: u8) {
-        let idx = self.to_idx(x, y);
-        if let Some(v) = self.field.get_mut(idx) {
-            *v |= bits;
-        }
+        Map { field }
     }
 }
 
 impl Display for Map {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for i in 0..self.field.len() {
-            let v = self.field[i];
-            let repr = match v {
-                0 => " ",
-                ENTRY => "E",
-                EXIT => "X",
-                WALL => "#",
-                LEFT => "<",
-                RIGHT => ">",
-                UP => "^",
-                DOWN => "v",
-                _ => match (1..=4).map(|bit| (v >> bit) & 1).sum::<u8>() {
-                    2 => "2",
-                    3 => "3",
-                    4 => "4",
-                    x => unreachable!("dafuq: x: {}, bits: {}", x, v)
-                }
-            };
-            write!(f, "{}", repr)?;
-            if i % self.dx == self.dx - 1 {
-                writeln!(f)?;
+        for row in self.field.rows() {
+            for &v in row {
+                let repr = match v {
+                    0 => " ",
+                    ENTRY => "E",
+                    EXIT => "X",
+                    WALL => "#",
+                    LEFT => "<",
+                    RIGHT => ">",
+                    UP => "^",
+                    DOWN => "v",
+                    _ => match (1..=4).map(|bit| (v >> bit) & 1).sum::<u8>() {
+                        2 => "2",
+                        3 => "3",
+                        4 => "4",
+                        _ => unreachable!("cell {v:#010b} is neither a single object nor 2 to 4 blizzards")
+                    }
+                };
+                write!(f, "{}", repr)?;
             }
+            writeln!(f)?;
         }
         Ok(())
     }
 }
 
 impl FromStr for Map {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines = s.split_whitespace()
-            .collect::<Vec<_>>();
-        let dx = lines[0].len();
-        let dy = lines.len();
-
-        let mut res = Map {
-            dx,
-            dy,
-            field: Vec::with_capacity(dx * dy),
-        };
-        
-        lines
-            .into_iter()
-            .flat_map(|line| line.chars())
-            .for_each(|c| {
-                let repr = match c {
-                    '#' => WALL,
-                    '.' => EMPTY,
-                    '<' => LEFT,
-                    '>' => RIGHT,
-                    '^' => UP,
-                    'v' => DOWN,
-                    _ => panic!("invalid input: {}", c),
-                };
-                res.field.push(repr);
-            });
-        Ok(res)
+        let field = Grid::parse(s, |c| match c {
+            '#' => Some(WALL),
+            '.' => Some(EMPTY),
+            '<' => Some(LEFT),
+            '>' => Some(RIGHT),
+            '^' => Some(UP),
+            'v' => Some(DOWN),
+            _ => None,
+        })?;
+        Ok(Map { field })
     }
 }
 
 #[derive(Hash, Eq, PartialEq, Clone, Copy)]
 struct State {
     map_variant: usize,
-    elf_x: usize,
-    elf_y: usize,
+    elf: Pos,
 }
 
 impl State {
-    fn success(&self, goal: (usize, usize)) -> bool {
-        self.elf_x == goal.0 && self.elf_y == goal.1
+    fn success(&self, goal: Pos) -> bool {
+        self.elf == goal
     }
 
-    fn heuristic(&self, goal: (usize, usize)) -> u32 {
+    fn heuristic(&self, goal: Pos) -> u32 {
         // manhattan distance
-        let dist = self.elf_x.abs_diff(goal.0) + self.elf_y.abs_diff(goal.1);
+        let dist = self.elf.x.abs_diff(goal.x) + self.elf.y.abs_diff(goal.y);
         dist as u32
     }
 
     fn successors(&self, maps: &[Map]) -> Vec<(Self, u32)> {
         let map_variant = (self.map_variant + 1) % maps.len();
-        let next_map = &maps[map_variant];
-
-        let mut possible_moves = Vec::new(); 
-        let x = self.elf_x;
-        let y = self.elf_y;
-        possible_moves.push((x, y));
-        if x > 0 {
-            possible_moves.push((x - 1, y));
-        }        
-        if x < next_map.dx - 1 {
-            possible_moves.push((x + 1, y));
-        }
-        if y < next_map.dy - 1 {
-            possible_moves.push((x, y + 1));
-        }
-        if y > 0 {
-            possible_moves.push((x, y - 1));
-        }
-        possible_moves.into_iter()
-            .filter(|&(x, y)| next_map
-                .get_xy(x, y)
-                .map(|v| v == EMPTY)
-                .unwrap_or(false)
-            )
-            .map(|(x, y)| State {
-                map_variant,
-                elf_x: x,
-                elf_y: y,
-            })
-            .zip(repeat(1))
+        let next_map = &maps[map_variant].field;
+
+        once(self.elf)
+            .chain(next_map.neighbors4(self.elf))
+            .filter(|&pos| next_map[pos] == EMPTY)
+            .map(|elf| (State { map_variant, elf }, 1))
             .collect()
     }
 }
 
-fn shortest_path(start: (usize, usize), goal: (usize, usize), time: usize, maps: &[Map]) -> u32 {
+fn shortest_path(start: Pos, goal: Pos, time: usize, maps: &[Map]) -> Result<u32> {
     let state = State {
         map_variant: time,
-        elf_x: start.0,
-        elf_y: start.1,
+        elf: start,
     };
-    let res = astar(&state, |s| s.successors(maps), |s| s.heuristic(goal), |s| s.success(goal));
-    let (_path, len) = res.expect("No path could be found!");
-    len 
+    let res = astar(state, |s| s.successors(maps), |s| s.heuristic(goal), |s| s.success(goal));
+    let (_path, len) = res.ok_or_else(|| Error::NoSolution(format!("there is no way from {start:?} to {goal:?}")))?;
+    Ok(len)
 }
 
-type Point = (usize, usize);
-struct ProcessedInput {
-    start: Point,
-    exit: Point,
+pub struct ProcessedInput {
+    start: Pos,
+    exit: Pos,
     maps: Vec<Map>,
 }
 
-fn process_input(input: &str) -> ProcessedInput {
-    let mut map = input.parse::<Map>().unwrap();
-    let possible_state_num = (map.dx - 2) * (map.dy - 2); // todo: lcm
+fn process_input(input: &str) -> Result<ProcessedInput> {
+    let mut map = input.parse::<Map>()?;
+    let (dx, dy) = (map.field.width(), map.field.height());
+    if dx < 3 || dy < 3 {
+        return Err(ParseError::at(input, input, "a map with walls all around").into());
+    }
+    let possible_state_num = (dx - 2) * (dy - 2); // todo: lcm
     let mut maps = Vec::with_capacity(possible_state_num);
 
     for _ in 0..possible_state_num {
@@ -257,53 +185,60 @@ fn process_input(input: &str) -> ProcessedInput {
         map = after_minute;
     }
 
-    let start_x = map.field.iter().position(|&b| b == EMPTY).unwrap();
-    let start_y = 0;
-    let exit_y = map.dy - 1;
-    let exit_x = map.field[(exit_y * map.dx)..].iter().position(|&b| b == EMPTY).unwrap();
-    let start = (start_x, start_y);
-    let exit = (exit_x, exit_y);
+    let start_x = map.field.row(0).iter().position(|&b| b == EMPTY)
+        .ok_or_else(|| ParseError::at(input, input, "a gap in the top wall"))?;
+    let last_line = input.trim_end().lines().last().unwrap_or(input);
+    let exit_x = map.field.row(dy - 1).iter().position(|&b| b == EMPTY)
+        .ok_or_else(|| ParseError::at(input, last_line, "a gap in the bottom wall"))?;
+    let start = Pos::new(start_x, 0);
+    let exit = Pos::new(exit_x, dy - 1);
 
-    ProcessedInput {
+    Ok(ProcessedInput {
         start,
         exit,
         maps
-    }
+    })
 }
 
-pub fn process_part1(input: &str) -> String {
-    let ProcessedInput { start, exit, maps } = process_input(input);
-    let len = shortest_path(start, exit, 0, &maps);
-    len.to_string()
-}
+pub struct Day24;
 
-pub fn process_part2(input: &str) -> String {
-    let ProcessedInput { start, exit, maps } = process_input(input);
+impl Solution for Day24 {
+    type Input<'a> = ProcessedInput;
 
-    let there = shortest_path(start, exit, 0, &maps);
-    let back = shortest_path(exit, start, there as usize, &maps);
-    let there_again = shortest_path(start, exit, (there + back) as usize, &maps);
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        process_input(input)
+    }
 
-    (there + back + there_again).to_string()
+    fn part1(input: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let ProcessedInput { start, exit, maps } = input;
+        let len = shortest_path(*start, *exit, 0, maps)?;
+        Ok(len.into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let ProcessedInput { start, exit, maps } = input;
+
+        let there = shortest_path(*start, *exit, 0, maps)?;
+        let back = shortest_path(*exit, *start, there as usize, maps)?;
+        let there_again = shortest_path(*start, *exit, (there + back) as usize, maps)?;
+
+        Ok((there + back + there_again).into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "#.######
-#>>.<^<#
-#.<..<<#
-#>v.><>#
-#<^v^^>#
-######.#";
 
-    #[test]
-    fn part1_works() {
-        assert_eq!("18", process_part1(INPUT));
-    }
+    aoc_core::examples!(Day24);
 
     #[test]
-    fn part2_works() {
-        assert_eq!("54", process_part2(INPUT));
+    fn bad_maps_are_errors() {
+        let Err(Error::Parse(err)) = Day24::parse("#.###\n#...#\n#####") else { panic!("there is no exit") };
+        assert_eq!((3, 1), (err.line, err.column));
+        assert!(Day24::parse("#.##\n#...#\n###.#").is_err());
+
+        let walled_in = Day24::parse("#.###\n#.#.#\n###.#").unwrap();
+        assert!(matches!(Day24::part1(&walled_in, &Params::default()), Err(Error::NoSolution(_))));
     }
 }
@@ -1,5 +1,6 @@
 use std::{str::FromStr, iter::Sum, fmt::Display};
 
+use aoc_core::{Answer, Error, Params, Result, Solution, parse_all};
 use nom::{character::complete::{line_ending, one_of}, multi::{many1, separated_list1}, IResult, Parser};
 
 struct Snafu {
@@ -60,21 +61,32 @@ impl Display  for Snafu {
 }
 
 fn snafu_nums(input: &str) -> IResult<&str, Vec<String>> {
-    let (_input, snafu_nums) = separated_list1(line_ending, many1(one_of("012-=")).map(|v| v.iter().collect::<String>()))(input)?;
+    let (input, snafu_nums) = separated_list1(line_ending, many1(one_of("012-=")).map(|v| v.iter().collect::<String>()))(input)?;
     Ok((input, snafu_nums))
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_input, snafu_nums) = snafu_nums(input).unwrap();
+pub struct Day25;
 
-    snafu_nums.iter()
-        .map(|s| s.parse::<Snafu>().unwrap())
-        .sum::<Snafu>()
-        .to_string()
-}
+impl Solution for Day25 {
+    type Input<'a> = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let snafu_nums = parse_all(input, snafu_nums)?;
+        Ok(snafu_nums)
+    }
 
-pub fn process_part2(input: &str) -> String {
-    input.into()
+    fn part1(snafu_nums: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(snafu_nums.iter()
+            .map(|s| s.parse::<Snafu>().unwrap())
+            .sum::<Snafu>()
+            .to_string()
+            .into())
+    }
+
+    fn part2(_snafu_nums: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        // day 25 has no second part
+        Err(Error::Unimplemented)
+    }
 }
 
 #[cfg(test)]
@@ -82,19 +94,8 @@ mod tests {
     use rstest::rstest;
 
     use super::*;
-    const INPUT: &str = "1=-0-2
-12111
-2=0=
-21
-2=01
-111
-20012
-112
-1=-1=
-1-12
-12
-1=
-122";
+
+    aoc_core::examples!(Day25);
 
     #[rstest]
     #[case("2", 2)] 
@@ -133,15 +134,4 @@ mod tests {
     fn from_snafu_works(#[case] input: &str, #[case] expected: i64) {
         assert_eq!(input.parse::<Snafu>().unwrap().value, expected);
     }
-
-    #[test]
-    fn part1_works() {
-        assert_eq!("2=-1=0", process_part1(INPUT));
-    }
-
-    #[test]
-    #[ignore = "not implemented"]
-    fn part2_works() {
-        assert_eq!("works", process_part2(INPUT));
-    }
 }
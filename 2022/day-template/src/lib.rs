@@ -1,25 +0,0 @@
-pub fn process_part1(input: &str) -> String {
-    input.into()
-}
-
-pub fn process_part2(input: &str) -> String {
-    input.into()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "";
-
-    #[test]
-    #[ignore = "not implemented"]
-    fn part1_works() {
-        assert_eq!("works", process_part1(INPUT));
-    }
-
-    #[test]
-    #[ignore = "not implemented"]
-    fn part2_works() {
-        assert_eq!("works", process_part2(INPUT));
-    }
-}
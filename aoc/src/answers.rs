@@ -0,0 +1,188 @@
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use aoc_core::Part;
+
+/// An answer recorded in a year's `answers.txt`, in the form `Answer` displays it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+}
+
+/// Why the site turned an answer down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Rejection::Wrong => "wrong",
+            Rejection::TooHigh => "too-high",
+            Rejection::TooLow => "too-low",
+        })
+    }
+}
+
+/// A submitted answer the site did not accept, recorded in a year's `rejected.txt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejected {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub rejection: Rejection,
+}
+
+/// Where `answers.txt` is for `year`, `root` being the workspace root.
+pub fn path(root: &Path, year: u16) -> PathBuf {
+    root.join(year.to_string()).join("answers.txt")
+}
+
+pub fn load(root: &Path, year: u16) -> io::Result<Vec<Expected>> {
+    let text = fs::read_to_string(path(root, year))?;
+    parse(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Appends an accepted answer to `answers.txt`.
+pub fn record(root: &Path, year: u16, expected: &Expected) -> io::Result<()> {
+    append(&path(root, year), &format!("{} {} {}", expected.day, expected.part, expected.answer))
+}
+
+pub fn rejected_path(root: &Path, year: u16) -> PathBuf {
+    root.join(year.to_string()).join("rejected.txt")
+}
+
+/// Loads the rejected answers of `year`, there are none when the file does not exist.
+pub fn load_rejected(root: &Path, year: u16) -> io::Result<Vec<Rejected>> {
+    let text = match fs::read_to_string(rejected_path(root, year)) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    parse_rejected(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+pub fn record_rejected(root: &Path, year: u16, rejected: &Rejected) -> io::Result<()> {
+    let Rejected { day, part, answer, rejection } = rejected;
+    append(&rejected_path(root, year), &format!("{day} {part} {answer} {rejection}"))
+}
+
+fn append(path: &Path, line: &str) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Parses `<day> <part> <answer>` lines. An empty answer is continued on the following lines
+/// indented by two spaces, one line per grid row. Empty lines and `#` comments are skipped.
+pub fn parse(text: &str) -> Result<Vec<Expected>, String> {
+    let mut answers: Vec<Expected> = Vec::new();
+    let mut open_grid = false;
+    for (idx, line) in text.lines().enumerate() {
+        let line_num = idx + 1;
+        if let Some(row) = line.strip_prefix("  ") {
+            match answers.last_mut() {
+                Some(expected) if open_grid => {
+                    if !expected.answer.is_empty() {
+                        expected.answer.push('\n');
+                    }
+                    expected.answer.push_str(row);
+                }
+                _ => return Err(format!("line {line_num}: grid row without a grid answer")),
+            }
+            continue;
+        }
+        open_grid = false;
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.splitn(3, ' ');
+        let day = fields.next()
+            .and_then(|day| day.parse::<u8>().ok())
+            .ok_or_else(|| format!("line {line_num}: expected a day"))?;
+        let part = fields.next()
+            .ok_or_else(|| format!("line {line_num}: expected a part"))?
+            .parse::<Part>()
+            .map_err(|err| format!("line {line_num}: {err}"))?;
+        let answer = fields.next().unwrap_or("").trim().to_string();
+        if answers.iter().any(|e| e.day == day && e.part == part) {
+            return Err(format!("line {line_num}: day {day} part {part} is listed twice"));
+        }
+        open_grid = answer.is_empty();
+        answers.push(Expected { day, part, answer });
+    }
+
+    match answers.iter().find(|e| e.answer.is_empty()) {
+        Some(e) => Err(format!("day {} part {} has an empty answer", e.day, e.part)),
+        None => Ok(answers),
+    }
+}
+
+/// Parses `<day> <part> <answer> <wrong|too-high|too-low>` lines, skipping empty lines and
+/// `#` comments.
+pub fn parse_rejected(text: &str) -> Result<Vec<Rejected>, String> {
+    let mut rejected = Vec::new();
+    for (idx, line) in text.lines().enumerate() {
+        let line_num = idx + 1;
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [day, part, answer, rejection] = fields[..] else {
+            return Err(format!("line {line_num}: expected <day> <part> <answer> <wrong|too-high|too-low>"));
+        };
+        let day = day.parse::<u8>().map_err(|_| format!("line {line_num}: expected a day"))?;
+        let part = part.parse::<Part>().map_err(|err| format!("line {line_num}: {err}"))?;
+        let rejection = match rejection {
+            "wrong" => Rejection::Wrong,
+            "too-high" => Rejection::TooHigh,
+            "too-low" => Rejection::TooLow,
+            other => return Err(format!("line {line_num}: unknown rejection '{other}'")),
+        };
+        rejected.push(Rejected { day, part, answer: answer.to_string(), rejection });
+    }
+    Ok(rejected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_works() {
+        let answers = parse("# comment\n1 1 24000\n\n5 2 MCD\n10 2\n  #..#\n  .##.\n").unwrap();
+        assert_eq!(vec![
+            Expected { day: 1, part: Part::One, answer: "24000".to_string() },
+            Expected { day: 5, part: Part::Two, answer: "MCD".to_string() },
+            Expected { day: 10, part: Part::Two, answer: "#..#\n.##.".to_string() },
+        ], answers);
+    }
+
+    #[test]
+    fn parse_reports_mistakes() {
+        assert_eq!(Err("line 1: invalid part '3', expected 1 or 2".to_string()), parse("1 3 24000"));
+        assert_eq!(Err("line 2: day 1 part 1 is listed twice".to_string()), parse("1 1 2\n1 1 3"));
+        assert_eq!(Err("day 10 part 2 has an empty answer".to_string()), parse("10 2\n11 1 5"));
+        assert!(parse("1 1 5\n  #.#").is_err());
+    }
+
+    #[test]
+    fn parse_rejected_works() {
+        let rejected = parse_rejected("# comment\n1 2 123 too-high\n\n5 1 ABC wrong\n").unwrap();
+        assert_eq!(vec![
+            Rejected { day: 1, part: Part::Two, answer: "123".to_string(), rejection: Rejection::TooHigh },
+            Rejected { day: 5, part: Part::One, answer: "ABC".to_string(), rejection: Rejection::Wrong },
+        ], rejected);
+        assert_eq!(Err("line 1: unknown rejection 'high'".to_string()), parse_rejected("1 2 123 high"));
+        assert!(parse_rejected("1 2 123").is_err());
+    }
+}
@@ -0,0 +1,325 @@
+use std::{
+    fmt::Display,
+    fs::File,
+    hint::black_box,
+    io::{self, Write},
+    path::Path,
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use aoc_core::{normalize, tracing::info_span, Answer, Error, Params, Part, Result, Solution, Variant};
+
+use crate::{days::Day, inputs::Inputs, memory::{self, Allocations}};
+
+#[derive(Clone)]
+pub struct Settings {
+    /// How many times each stage is run at most.
+    pub runs: usize,
+    /// A stage stops repeating once its runs add up to this, there is always at least one run.
+    pub budget: Duration,
+    /// Longest wait for a stage before the day is given up.
+    pub timeout: Duration,
+    /// Also time the variants of the parts, after the parts.
+    pub variants: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+    /// A variant of a part, timed including parsing since variants bring their own.
+    Variant(Part, &'static str),
+}
+
+impl Stage {
+    /// The stages `measure` goes through for `day`, in order.
+    fn of(day: &Day, settings: &Settings) -> Vec<Stage> {
+        let mut stages = vec![Stage::Parse, Stage::Part(Part::One), Stage::Part(Part::Two)];
+        if settings.variants {
+            stages.extend(day.variants.iter().map(|v| Stage::Variant(v.part, v.name)));
+        }
+        stages
+    }
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => f.write_str("parse"),
+            Stage::Part(part) => write!(f, "part{part}"),
+            Stage::Variant(part, name) => write!(f, "part{part}:{name}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    /// What the last run allocated, with the `count-allocations` feature.
+    pub allocations: Option<Allocations>,
+}
+
+impl Stats {
+    fn new(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "a stage runs at least once");
+        samples.sort();
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        Stats {
+            runs: samples.len(),
+            median,
+            min: samples[0],
+            max: samples[samples.len() - 1],
+            allocations: None,
+        }
+    }
+}
+
+pub enum Outcome {
+    Timed(Stats),
+    /// A part that was timed, with the answer of its last run.
+    Answered(Stats, Answer),
+    Failed(Error),
+    TimedOut,
+    Skipped,
+}
+
+/// Repeats `f` until either the number of runs or the time budget is used up.
+fn repeat<T>(settings: &Settings, mut f: impl FnMut() -> T) -> (Stats, T) {
+    let mut samples = Vec::with_capacity(settings.runs);
+    let mut total = Duration::ZERO;
+    loop {
+        memory::start_counting();
+        let start = Instant::now();
+        let result = black_box(f());
+        let elapsed = start.elapsed();
+        let allocations = memory::allocations();
+        samples.push(elapsed);
+        total += elapsed;
+        if samples.len() >= settings.runs || total >= settings.budget {
+            return (Stats { allocations, ..Stats::new(samples) }, result);
+        }
+    }
+}
+
+/// Times parsing, both parts and possibly the variants of `S`, sending one outcome per stage
+/// in `Stage::of` order. Stops early when parsing fails.
+pub fn measure<S: Solution>(input: &str, settings: &Settings, outcomes: &Sender<Outcome>) {
+    let input = normalize(input);
+    let params = Params::defaults(S::PARAMS);
+    let (stats, parsed) = repeat(settings, || S::parse(&input));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            let _ = outcomes.send(Outcome::Failed(err));
+            return;
+        }
+    };
+    if outcomes.send(Outcome::Timed(stats)).is_err() {
+        return;
+    }
+
+    for part in Part::ALL {
+        let (stats, answer) = repeat(settings, || match part {
+            Part::One => S::part1(&parsed, &params),
+            Part::Two => S::part2(&parsed, &params),
+        });
+        let outcome = match answer {
+            Ok(answer) => Outcome::Answered(stats, answer),
+            Err(err) => Outcome::Failed(err),
+        };
+        if outcomes.send(outcome).is_err() {
+            return;
+        }
+    }
+    if !settings.variants {
+        return;
+    }
+
+    for variant in S::VARIANTS {
+        let (stats, answer) = repeat(settings, || (variant.solve)(&input, &params));
+        let outcome = match answer {
+            Ok(answer) => Outcome::Answered(stats, answer),
+            Err(err) => Outcome::Failed(err),
+        };
+        if outcomes.send(outcome).is_err() {
+            return;
+        }
+    }
+}
+
+/// One part solved once.
+pub struct Timed {
+    pub answer: Answer,
+    /// Not known for variants, which parse the input themselves.
+    pub parse: Option<Duration>,
+    pub solve: Duration,
+    /// What parsing and solving allocated, with the `count-allocations` feature.
+    pub allocations: Option<Allocations>,
+}
+
+/// Like `Solution::solve_variant`, but times parsing and solving apart.
+pub fn timed<S: Solution>(input: &str, part: Part, variant: &str, params: &Params) -> Result<Timed> {
+    let params = &params.with_defaults(S::PARAMS)?;
+    let input = normalize(input);
+    memory::start_counting();
+    if variant != Variant::DEFAULT {
+        let variant = S::VARIANTS.iter()
+            .find(|v| v.part == part && v.name == variant)
+            .ok_or_else(|| Error::UnknownVariant(variant.to_string()))?;
+        let start = Instant::now();
+        let answer = (variant.solve)(&input, params)?;
+        let solve = start.elapsed();
+        return Ok(Timed { answer, parse: None, solve, allocations: memory::allocations() });
+    }
+
+    let start = Instant::now();
+    let parsed = S::parse(&input)?;
+    let parse = start.elapsed();
+    let start = Instant::now();
+    let answer = match part {
+        Part::One => S::part1(&parsed, params),
+        Part::Two => S::part2(&parsed, params),
+    }?;
+    let solve = start.elapsed();
+    Ok(Timed { answer, parse: Some(parse), solve, allocations: memory::allocations() })
+}
+
+pub struct Row {
+    pub year: u16,
+    pub day: u8,
+    pub stage: Stage,
+    pub outcome: Outcome,
+}
+
+/// Benchmarks the given days one after another. A day that runs into the timeout is left
+/// behind on its thread, so it keeps a core busy until the process exits.
+pub fn run(days: &[&Day], settings: &Settings, inputs: &Inputs) -> Vec<Row> {
+    let mut rows = Vec::new();
+    for day in days {
+        let input = match inputs.get(day.year, day.day) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("skipping {} day {}: {err}", day.year, day.day);
+                continue;
+            }
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        let measure = day.measure;
+        let thread_settings = settings.clone();
+        let span = info_span!("solve", year = day.year, day = day.day);
+        thread::spawn(move || span.in_scope(|| measure(&input, &thread_settings, &sender)));
+
+        let mut given_up = false;
+        for stage in Stage::of(day, settings) {
+            let outcome = if given_up {
+                Outcome::Skipped
+            } else {
+                match receiver.recv_timeout(settings.timeout) {
+                    Ok(outcome) => outcome,
+                    Err(RecvTimeoutError::Timeout) => {
+                        given_up = true;
+                        Outcome::TimedOut
+                    }
+                    Err(RecvTimeoutError::Disconnected) => Outcome::Skipped,
+                }
+            };
+            print_row(day.year, day.day, stage, &outcome);
+            rows.push(Row { year: day.year, day: day.day, stage, outcome });
+        }
+    }
+    rows
+}
+
+fn print_row(year: u16, day: u8, stage: Stage, outcome: &Outcome) {
+    let label = format!("{year} {day:02} {stage}");
+    match outcome {
+        Outcome::Timed(stats) | Outcome::Answered(stats, _) => {
+            print!("{label:<25} {:>5} {:>12.1?} {:>12.1?} {:>12.1?}", stats.runs, stats.median, stats.min, stats.max);
+            if let Some(allocations) = stats.allocations {
+                print!(
+                    " {:>10} {:>12} {:>12}",
+                    allocations.count,
+                    memory::format_bytes(allocations.bytes),
+                    memory::format_bytes(allocations.peak_heap)
+                );
+            }
+            println!();
+        }
+        Outcome::Failed(err) => println!("{label:<25} {err}"),
+        Outcome::TimedOut => println!("{label:<25} timed out"),
+        Outcome::Skipped => println!("{label:<25} -"),
+    }
+}
+
+pub fn print_header() {
+    print!("{:<25} {:>5} {:>12} {:>12} {:>12}", "stage", "runs", "median", "min", "max");
+    if memory::COUNTING {
+        print!(" {:>10} {:>12} {:>12}", "allocs", "allocated", "peak heap");
+    }
+    println!();
+}
+
+/// Writes the results as CSV, durations in nanoseconds. The allocation columns are only
+/// filled in with the `count-allocations` feature.
+pub fn write_csv(rows: &[Row], path: &Path) -> io::Result<()> {
+    let mut out = io::BufWriter::new(File::create(path)?);
+    writeln!(out, "year,day,stage,status,runs,median_ns,min_ns,max_ns,allocations,allocated_bytes,peak_heap_bytes")?;
+    for Row { year, day, stage, outcome } in rows {
+        match outcome {
+            Outcome::Timed(stats) | Outcome::Answered(stats, _) => {
+                let allocations = stats.allocations.map_or_else(
+                    || ",,".to_string(),
+                    |a| format!("{},{},{}", a.count, a.bytes, a.peak_heap),
+                );
+                writeln!(
+                    out,
+                    "{year},{day},{stage},ok,{},{},{},{},{allocations}",
+                    stats.runs, stats.median.as_nanos(), stats.min.as_nanos(), stats.max.as_nanos()
+                )?
+            }
+            Outcome::Failed(_) => writeln!(out, "{year},{day},{stage},failed,0,,,,,,")?,
+            Outcome::TimedOut => writeln!(out, "{year},{day},{stage},timeout,0,,,,,,")?,
+            Outcome::Skipped => writeln!(out, "{year},{day},{stage},skipped,0,,,,,,")?,
+        }
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn stats_works() {
+        let stats = Stats::new(vec![ms(5), ms(1), ms(3)]);
+        assert_eq!(Stats { runs: 3, median: ms(3), min: ms(1), max: ms(5), allocations: None }, stats);
+        assert_eq!(ms(2), Stats::new(vec![ms(4), ms(1), ms(3), ms(1)]).median);
+    }
+
+    #[test]
+    fn repeat_stops_at_budget() {
+        let settings = Settings { runs: 1000, budget: ms(5), timeout: ms(100), variants: false };
+        let (stats, _) = repeat(&settings, || thread::sleep(ms(2)));
+        // every run takes at least 2ms, so the budget is used up after 3 runs at the latest
+        assert!(stats.runs <= 3);
+
+        let settings = Settings { runs: 4, budget: Duration::from_secs(1), timeout: ms(100), variants: false };
+        let (stats, _) = repeat(&settings, || ());
+        assert_eq!(4, stats.runs);
+    }
+}
@@ -0,0 +1,82 @@
+use std::sync::mpsc::Sender;
+
+use aoc_core::{Answer, Param, Params, Part, Result, Solution, Variant};
+
+use crate::bench;
+
+/// Solves a part with the named variant.
+pub type Solver = fn(&str, Part, &str, &Params) -> Result<Answer>;
+pub type Timer = fn(&str, Part, &str, &Params) -> Result<bench::Timed>;
+pub type Measure = fn(&str, &bench::Settings, &Sender<bench::Outcome>);
+
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub solve: Solver,
+    pub timed: Timer,
+    pub params: &'static [Param],
+    pub variants: &'static [Variant],
+    pub measure: Measure,
+}
+
+impl Day {
+    /// Names of the implementations of `part`, the default one first.
+    pub fn variants_of(&self, part: Part) -> impl Iterator<Item = &'static str> + '_ {
+        let variants = self.variants.iter().filter(move |v| v.part == part).map(|v| v.name);
+        std::iter::once(Variant::DEFAULT).chain(variants)
+    }
+}
+
+macro_rules! day {
+    ($year:literal, $day:literal, $solution:ty) => {
+        Day {
+            year: $year,
+            day: $day,
+            solve: <$solution as Solution>::solve_variant,
+            timed: bench::timed::<$solution>,
+            params: <$solution as Solution>::PARAMS,
+            variants: <$solution as Solution>::VARIANTS,
+            measure: bench::measure::<$solution>,
+        }
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day!(2022, 1, aoc_2022_day_01::Day01),
+    day!(2022, 2, aoc_2022_day_02::Day02),
+    day!(2022, 3, aoc_2022_day_03::Day03),
+    day!(2022, 4, aoc_2022_day_04::Day04),
+    day!(2022, 5, aoc_2022_day_05::Day05),
+    day!(2022, 6, aoc_2022_day_06::Day06),
+    day!(2022, 7, aoc_2022_day_07::Day07),
+    day!(2022, 8, aoc_2022_day_08::Day08),
+    day!(2022, 9, aoc_2022_day_09::Day09),
+    day!(2022, 10, aoc_2022_day_10::Day10),
+    day!(2022, 11, aoc_2022_day_11::Day11),
+    day!(2022, 12, aoc_2022_day_12::Day12),
+    day!(2022, 13, aoc_2022_day_13::Day13),
+    day!(2022, 14, aoc_2022_day_14::Day14),
+    day!(2022, 15, aoc_2022_day_15::Day15),
+    day!(2022, 16, aoc_2022_day_16::Day16),
+    day!(2022, 17, aoc_2022_day_17::Day17),
+    day!(2022, 18, aoc_2022_day_18::Day18),
+    day!(2022, 19, aoc_2022_day_19::Day19),
+    day!(2022, 20, aoc_2022_day_20::Day20),
+    day!(2022, 21, aoc_2022_day_21::Day21),
+    day!(2022, 22, aoc_2022_day_22::Day22),
+    day!(2022, 23, aoc_2022_day_23::Day23),
+    day!(2022, 24, aoc_2022_day_24::Day24),
+    day!(2022, 25, aoc_2022_day_25::Day25),
+];
+
+/// Every year with at least one registered day, in order.
+pub fn years() -> Vec<u16> {
+    let mut years: Vec<u16> = DAYS.iter().map(|d| d.year).collect();
+    years.sort();
+    years.dedup();
+    years
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
@@ -0,0 +1,108 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use aoc_core::normalize;
+
+use crate::{
+    site::{self, Site, SiteError},
+    workspace_root,
+};
+
+/// Hands out puzzle inputs, downloading the ones that are not cached yet.
+pub struct Inputs {
+    /// Inputs are cached below this directory as `<year>/day-NN/input.txt`.
+    dir: PathBuf,
+    site: Site,
+}
+
+impl Inputs {
+    pub fn new(dir: PathBuf, site: Site) -> Self {
+        Inputs { dir, site }
+    }
+
+    /// Caches inputs in `AOC_INPUT_DIR`, or next to the days' crates when it is not set.
+    pub fn from_env() -> Self {
+        let dir = env::var_os("AOC_INPUT_DIR").map_or_else(|| workspace_root().to_path_buf(), PathBuf::from);
+        Inputs::new(dir, Site::new(site::Config::from_env()))
+    }
+
+    pub fn site(&self) -> &Site {
+        &self.site
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir.join(year.to_string()).join(format!("day-{day:02}")).join("input.txt")
+    }
+
+    /// Returns the normalized input of the given day.
+    pub fn get(&self, year: u16, day: u8) -> Result<String, String> {
+        let path = self.path(year, day);
+        if path.exists() {
+            let input = fs::read_to_string(&path).map_err(|err| format!("could not read {}: {err}", path.display()))?;
+            return Ok(normalize(&input).into_owned());
+        }
+
+        let input = match self.site.get(&format!("/{year}/day/{day}/input")) {
+            Ok(input) => input,
+            Err(SiteError::Status(404)) => return Err(format!("{year} day {day} is not unlocked yet")),
+            Err(err @ SiteError::NoSession) =>
+                return Err(format!("no input at {} to fall back on: {err}", path.display())),
+            Err(err) => return Err(format!("could not download the input of {year} day {day}: {err}")),
+        };
+        let write = |path: &Path| {
+            fs::create_dir_all(path.parent().expect("input path has a parent"))?;
+            fs::write(path, &input)
+        };
+        write(&path).map_err(|err| format!("could not write {}: {err}", path.display()))?;
+        Ok(normalize(&input).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::site::tests::{ok, site, stub_server};
+
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-inputs-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn get_downloads_and_caches() {
+        let (base_url, requests) = stub_server(vec![ok("1000\r\n2\n")]);
+        let dir = temp_dir("download");
+        let inputs = Inputs::new(dir.clone(), site(base_url));
+
+        assert_eq!(Ok("1000\n2".to_string()), inputs.get(2022, 1));
+        assert_eq!("GET /2022/day/1/input HTTP/1.1", requests.recv().unwrap().head[0]);
+        assert!(dir.join("2022/day-01/input.txt").exists());
+
+        // the stub only answers once, so this has to come from the cache
+        assert_eq!(Ok("1000\n2".to_string()), inputs.get(2022, 1));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn get_reports_missing_session() {
+        let dir = temp_dir("session");
+        let site = Site::new(site::Config { base_url: "http://127.0.0.1:9".to_string(), session: None, min_interval: Duration::ZERO });
+        let err = Inputs::new(dir, site).get(2022, 1).unwrap_err();
+        assert!(err.contains("no session token"), "{err}");
+    }
+
+    #[test]
+    fn get_reports_locked_days() {
+        let (base_url, _requests) = stub_server(vec!["HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_string()]);
+        let dir = temp_dir("locked");
+        let err = Inputs::new(dir.clone(), site(base_url)).get(2022, 25).unwrap_err();
+        assert_eq!("2022 day 25 is not unlocked yet", err);
+        assert!(!dir.join("2022/day-25/input.txt").exists());
+    }
+}
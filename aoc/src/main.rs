@@ -0,0 +1,416 @@
+mod answers;
+mod bench;
+mod days;
+mod inputs;
+mod memory;
+mod report;
+mod run_all;
+mod scaffold;
+mod site;
+mod submit;
+mod verify;
+
+use std::{path::{Path, PathBuf}, process::ExitCode, time::Duration};
+
+use aoc_core::{read_input, tracing::info_span, Error, Params, Part, Variant};
+use clap::{Args, Parser, Subcommand};
+use inputs::Inputs;
+use report::{Format, Record};
+
+/// Runs Advent of Code solutions, e.g. `aoc 2022 12 2`.
+#[derive(Parser)]
+#[command(version, args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    run: RunArgs,
+    /// Shows the solutions' diagnostics on stderr, e.g. `debug` or `aoc_2022_day_17=trace`
+    #[arg(long, global = true, value_name = "FILTER")]
+    log: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solves every registered day in parallel and compares the answers with the recorded ones
+    Run(RunAllArgs),
+    /// Times parsing and both parts of every day, or of the selected ones
+    Bench(BenchArgs),
+    /// Checks the solutions of every year, or of the selected one, against the answers
+    /// recorded in `<year>/answers.txt`
+    Verify(VerifyArgs),
+    /// Creates the crate for a new day from the template and registers it in the runner
+    New(NewArgs),
+    /// Submits the answer to a part and records the verdict next to the known answers
+    Submit(SubmitArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Puzzle year
+    #[arg(required = true)]
+    year: Option<u16>,
+    /// Puzzle day
+    #[arg(required = true)]
+    day: Option<u8>,
+    /// Part to run, both parts are run when omitted
+    part: Option<Part>,
+    /// Puzzle input file, defaults to the day's input.txt, which is downloaded when missing
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Overrides a puzzle parameter, e.g. `--param row=10`, can be repeated
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+    params: Vec<(String, String)>,
+    /// Runs this implementation of the parts instead of the default one
+    #[arg(long, default_value = Variant::DEFAULT)]
+    variant: String,
+    /// How results are printed
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+fn parse_param(s: &str) -> Result<(String, String), String> {
+    let (name, value) = s.split_once('=').ok_or_else(|| format!("expected NAME=VALUE, got '{s}'"))?;
+    Ok((name.trim().to_string(), value.trim().to_string()))
+}
+
+#[derive(Args)]
+struct RunAllArgs {
+    /// Run every registered day, `aoc <year> <day>` runs a single one
+    #[arg(long, required = true)]
+    all: bool,
+    /// Give up on a part after this many seconds, enough for the slowest recorded part (day 16
+    /// part 2, about 80s)
+    #[arg(long, default_value_t = 120.0)]
+    timeout: f64,
+    /// Number of days solved at the same time, defaults to the number of cores
+    #[arg(long)]
+    jobs: Option<usize>,
+    /// How results are printed
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    /// Puzzle year
+    year: u16,
+    /// Puzzle day
+    day: u8,
+}
+
+#[derive(Args)]
+struct SubmitArgs {
+    /// Puzzle year
+    year: u16,
+    /// Puzzle day
+    day: u8,
+    /// Part to submit
+    part: Part,
+    /// Answer to submit instead of the one the solution gives, e.g. letters read off a grid
+    #[arg(long)]
+    answer: Option<String>,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// Only verify this year
+    year: Option<u16>,
+    /// Only verify this day
+    day: Option<u8>,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// Only benchmark this year
+    year: Option<u16>,
+    /// Only benchmark this day
+    day: Option<u8>,
+    /// Maximum number of runs per stage
+    #[arg(long, default_value_t = 10)]
+    runs: usize,
+    /// Stop repeating a stage after this many seconds
+    #[arg(long, default_value_t = 3.0)]
+    budget: f64,
+    /// Give up on a day when one of its stages takes longer than this many seconds
+    #[arg(long, default_value_t = 120.0)]
+    timeout: f64,
+    /// Also write the results to this CSV file
+    #[arg(long)]
+    csv: Option<PathBuf>,
+}
+
+fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("runner lives inside the workspace")
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if let Some(filter) = &cli.log {
+        if let Err(err) = init_logging(filter) {
+            eprintln!("error: invalid --log filter: {err}");
+            return ExitCode::FAILURE;
+        }
+    }
+    match cli.command {
+        Some(Command::Run(args)) => run_all(args),
+        Some(Command::Bench(args)) => bench(args),
+        Some(Command::Verify(args)) => verify(args),
+        Some(Command::New(args)) => new(args),
+        Some(Command::Submit(args)) => submit(args),
+        None => run(cli.run),
+    }
+}
+
+/// Sends the events the filter lets through to stderr, so they never mix with answers.
+/// The filter uses `tracing_subscriber::EnvFilter` syntax: a level, `target=level` pairs (the
+/// target of a day is its crate, e.g. `aoc_2022_day_17`) or span fields, e.g. `[solve{day=17}]=debug`.
+fn init_logging(filter: &str) -> Result<(), String> {
+    let filter = tracing_subscriber::EnvFilter::try_new(filter).map_err(|err| err.to_string())?;
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .without_time()
+        .init();
+    Ok(())
+}
+
+fn run(args: RunArgs) -> ExitCode {
+    let (Some(year), Some(day_num)) = (args.year, args.day) else {
+        unreachable!("clap requires year and day");
+    };
+
+    let Some(day) = days::find(year, day_num) else {
+        eprintln!("error: no solution registered for {year} day {day_num}");
+        return ExitCode::FAILURE;
+    };
+
+    // without a part, only the parts that have the variant are run
+    let parts: Vec<Part> = match args.part {
+        Some(part) => vec![part],
+        None => Part::ALL.into_iter()
+            .filter(|&part| day.variants_of(part).any(|name| name == args.variant))
+            .collect(),
+    };
+    if parts.is_empty() {
+        eprintln!("error: {year} day {day_num}: {}", Error::UnknownVariant(args.variant));
+        print_variants(day);
+        return ExitCode::FAILURE;
+    }
+
+    let input = match args.input {
+        Some(path) => read_input(&path).map_err(|err| format!("could not read {}: {err}", path.display())),
+        None => Inputs::from_env().get(year, day_num),
+    };
+    let input = match input {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let params: Params = args.params.into_iter().collect();
+    if args.format == Format::Csv {
+        println!("{}", report::CSV_HEADER);
+    }
+    let mut status = ExitCode::SUCCESS;
+    for part in parts {
+        let span = info_span!("solve", year, day = day_num, part = %part, variant = %args.variant);
+        memory::reset_peak();
+        let result = span.in_scope(|| (day.timed)(&input, part, &args.variant, &params));
+        let peak_memory = memory::peak_kb();
+        if let Err(err) = &result {
+            eprintln!("error: {year} day {day_num} part {part}: {err}");
+            match err {
+                Error::UnknownParam(_) => {
+                    print_params(day);
+                    return ExitCode::FAILURE;
+                }
+                Error::UnknownVariant(_) => {
+                    print_variants(day);
+                    return ExitCode::FAILURE;
+                }
+                _ => status = ExitCode::FAILURE,
+            }
+        }
+
+        let record = Record {
+            year,
+            day: day_num,
+            part,
+            variant: args.variant.clone(),
+            status: match &result {
+                Ok(_) => "ok".to_string(),
+                Err(Error::Unimplemented) => "unimplemented".to_string(),
+                Err(err) => format!("failed: {err}"),
+            },
+            answer: result.as_ref().ok().map(|timed| timed.answer.to_string()),
+            parse: result.as_ref().ok().and_then(|timed| timed.parse),
+            solve: result.as_ref().ok().map(|timed| timed.solve),
+            peak_memory,
+            allocations: result.as_ref().ok().and_then(|timed| timed.allocations),
+        };
+        match args.format {
+            Format::Text => {
+                if let Some(answer) = record.answer {
+                    println!("{answer}");
+                }
+            }
+            Format::Json => println!("{}", record.to_json()),
+            Format::Csv => println!("{}", record.to_csv()),
+        }
+    }
+    status
+}
+
+fn print_params(day: &days::Day) {
+    if day.params.is_empty() {
+        eprintln!("{} day {} takes no parameters", day.year, day.day);
+        return;
+    }
+    eprintln!("{} day {} takes:", day.year, day.day);
+    for param in day.params {
+        let setting = format!("{}={}", param.name, param.default);
+        eprintln!("  {setting:<25} {}", param.about);
+    }
+}
+
+fn run_all(args: RunAllArgs) -> ExitCode {
+    let days: Vec<_> = days::DAYS.iter().collect();
+    let inputs = Inputs::from_env();
+    let timeout = Duration::from_secs_f64(args.timeout);
+    let rows = match run_all::run(&days, &inputs, workspace_root(), timeout, args.jobs) {
+        Ok(rows) => rows,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match args.format {
+        Format::Text => run_all::print_table(&rows),
+        Format::Json => rows.iter().for_each(|row| println!("{}", row.record().to_json())),
+        Format::Csv => {
+            println!("{}", report::CSV_HEADER);
+            rows.iter().for_each(|row| println!("{}", row.record().to_csv()));
+        }
+    }
+    if rows.iter().any(|row| row.status.is_failure()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn print_variants(day: &days::Day) {
+    eprintln!("{} day {} has:", day.year, day.day);
+    for part in Part::ALL {
+        eprintln!("  part {part}: {}", day.variants_of(part).collect::<Vec<_>>().join(", "));
+    }
+}
+
+fn bench(args: BenchArgs) -> ExitCode {
+    let selected: Vec<_> = days::DAYS.iter()
+        .filter(|d| args.year.is_none_or(|year| d.year == year))
+        .filter(|d| args.day.is_none_or(|day| d.day == day))
+        .collect();
+    if selected.is_empty() {
+        eprintln!("error: no solutions match the selection");
+        return ExitCode::FAILURE;
+    }
+
+    let settings = bench::Settings {
+        runs: args.runs.max(1),
+        budget: Duration::from_secs_f64(args.budget),
+        timeout: Duration::from_secs_f64(args.timeout),
+        variants: true,
+    };
+    bench::print_header();
+    let inputs = Inputs::from_env();
+    let rows = bench::run(&selected, &settings, &inputs);
+
+    if let Some(path) = args.csv {
+        if let Err(err) = bench::write_csv(&rows, &path) {
+            eprintln!("error: could not write {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn verify(args: VerifyArgs) -> ExitCode {
+    let years = match args.year {
+        Some(year) => vec![year],
+        None => days::years(),
+    };
+    let inputs = Inputs::from_env();
+    let mut status = ExitCode::SUCCESS;
+    for year in years {
+        match verify::run(year, args.day, &inputs) {
+            Ok(true) => {}
+            Ok(false) => status = ExitCode::FAILURE,
+            Err(err) => {
+                eprintln!("error: could not load {}: {err}", answers::path(workspace_root(), year).display());
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    status
+}
+
+fn new(args: NewArgs) -> ExitCode {
+    match scaffold::create(workspace_root(), args.year, args.day) {
+        Ok(dir) => {
+            println!("created {}", dir.display());
+            println!("run it with `aoc {0} {1}`, benchmark it with `aoc bench {0} {1}`", args.year, args.day);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn submit(args: SubmitArgs) -> ExitCode {
+    let inputs = Inputs::from_env();
+    let answer = match args.answer {
+        Some(answer) => Ok(answer.trim().to_string()),
+        None => solve(&inputs, args.year, args.day, args.part),
+    };
+    let answer = match answer {
+        Ok(answer) => answer,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("submitting {answer} for {} day {} part {}", args.year, args.day, args.part);
+    let submission = submit::Submission { year: args.year, day: args.day, part: args.part, answer };
+    match submit::submit(inputs.site(), workspace_root(), &submission) {
+        Ok(verdict @ submit::Verdict::Right) => {
+            println!("{verdict}, recorded in {}", answers::path(workspace_root(), args.year).display());
+            ExitCode::SUCCESS
+        }
+        Ok(verdict) => {
+            println!("{verdict}");
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Solves a part of a registered day on its own input with the default parameters.
+fn solve(inputs: &Inputs, year: u16, day_num: u8, part: Part) -> Result<String, String> {
+    let day = days::find(year, day_num).ok_or_else(|| format!("no solution registered for {year} day {day_num}"))?;
+    let input = inputs.get(year, day_num)?;
+    let answer = (day.solve)(&input, part, Variant::DEFAULT, &Params::default())
+        .map_err(|err| format!("{year} day {day_num} part {part}: {err}"))?;
+    Ok(answer.to_string())
+}
@@ -0,0 +1,168 @@
+use std::fs;
+
+#[cfg(feature = "count-allocations")]
+pub use counting::{allocations, start_counting};
+
+/// Whether the runner was built with the counting allocator.
+pub const COUNTING: bool = cfg!(feature = "count-allocations");
+
+/// Heap usage counted by the allocator of the `count-allocations` feature. The counters are
+/// shared by all threads, so they only describe one solution when nothing else runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocations {
+    /// Allocations made, reallocations included.
+    pub count: u64,
+    /// Bytes requested by those allocations.
+    pub bytes: u64,
+    /// Most heap in use at once, including what was allocated before counting started.
+    pub peak_heap: u64,
+}
+
+#[cfg(feature = "count-allocations")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicU64, Ordering::Relaxed},
+    };
+
+    use super::Allocations;
+
+    static COUNT: AtomicU64 = AtomicU64::new(0);
+    static BYTES: AtomicU64 = AtomicU64::new(0);
+    static CURRENT: AtomicU64 = AtomicU64::new(0);
+    static PEAK: AtomicU64 = AtomicU64::new(0);
+
+    struct Counting;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    fn allocated(size: usize) {
+        COUNT.fetch_add(1, Relaxed);
+        BYTES.fetch_add(size as u64, Relaxed);
+        let current = CURRENT.fetch_add(size as u64, Relaxed) + size as u64;
+        PEAK.fetch_max(current, Relaxed);
+    }
+
+    fn freed(size: usize) {
+        CURRENT.fetch_sub(size as u64, Relaxed);
+    }
+
+    // SAFETY: every call is passed on to the system allocator unchanged, the counting around it
+    // does not allocate.
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) };
+            freed(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new_ptr.is_null() {
+                freed(layout.size());
+                allocated(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    /// Resets the counters, the peak starts at the heap in use right now.
+    pub fn start_counting() {
+        COUNT.store(0, Relaxed);
+        BYTES.store(0, Relaxed);
+        PEAK.store(CURRENT.load(Relaxed), Relaxed);
+    }
+
+    /// What was allocated since `start_counting`.
+    pub fn allocations() -> Option<Allocations> {
+        Some(Allocations { count: COUNT.load(Relaxed), bytes: BYTES.load(Relaxed), peak_heap: PEAK.load(Relaxed) })
+    }
+}
+
+/// Without the `count-allocations` feature nothing is counted.
+#[cfg(not(feature = "count-allocations"))]
+pub fn start_counting() {}
+
+#[cfg(not(feature = "count-allocations"))]
+pub fn allocations() -> Option<Allocations> {
+    None
+}
+
+/// Peak resident set size of the process in KiB, read from `VmHWM` in `/proc/self/status`.
+/// `None` where there is no procfs.
+pub fn peak_kb() -> Option<u64> {
+    parse_peak(&fs::read_to_string("/proc/self/status").ok()?)
+}
+
+/// Resets the peak to the current resident set size, so the next `peak_kb` only covers what
+/// ran in between. Without procfs, or when the kernel refuses, the peak keeps covering the
+/// whole process.
+pub fn reset_peak() {
+    let _ = fs::write("/proc/self/clear_refs", "5");
+}
+
+/// Formats a byte count in binary units, e.g. `1.5 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+fn parse_peak(status: &str) -> Option<u64> {
+    let line = status.lines().find_map(|line| line.strip_prefix("VmHWM:"))?;
+    line.trim().strip_suffix("kB")?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_peak_works() {
+        assert_eq!(Some(1788), parse_peak("VmPeak:\t   10000 kB\nVmHWM:\t    1788 kB\nVmRSS:\t    1700 kB\n"));
+        assert_eq!(None, parse_peak("VmRSS:\t    1700 kB\n"));
+    }
+
+    #[test]
+    fn format_bytes_works() {
+        assert_eq!("512 B", format_bytes(512));
+        assert_eq!("1.5 KiB", format_bytes(1536));
+        assert_eq!("3.0 GiB", format_bytes(3 << 30));
+    }
+
+    #[test]
+    #[cfg(feature = "count-allocations")]
+    fn allocations_are_counted() {
+        start_counting();
+        let v: Vec<u64> = Vec::with_capacity(1000);
+        let counted = allocations().unwrap();
+        drop(v);
+        assert!(counted.count >= 1);
+        assert!(counted.bytes >= 8000);
+        assert!(counted.peak_heap >= 8000);
+    }
+}
@@ -0,0 +1,144 @@
+use std::{fmt::Write, time::Duration};
+
+use aoc_core::Part;
+use clap::ValueEnum;
+
+use crate::memory::Allocations;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// For people: answers only, or a table for `run --all`
+    Text,
+    /// One JSON object per line
+    Json,
+    /// CSV with a header line
+    Csv,
+}
+
+/// The result of one part, as written in the machine-readable formats.
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub variant: String,
+    pub status: String,
+    pub answer: Option<String>,
+    pub parse: Option<Duration>,
+    pub solve: Option<Duration>,
+    /// Peak resident set size in KiB.
+    pub peak_memory: Option<u64>,
+    /// Only counted with the `count-allocations` feature.
+    pub allocations: Option<Allocations>,
+}
+
+pub const CSV_HEADER: &str =
+    "year,day,part,variant,status,answer,parse_ns,solve_ns,peak_memory_kb,allocations,allocated_bytes,peak_heap_bytes";
+
+impl Record {
+    /// Durations are in nanoseconds, missing values are `null`.
+    pub fn to_json(&self) -> String {
+        let mut json = format!(
+            "{{\"year\":{},\"day\":{},\"part\":{},\"variant\":{},\"status\":{},\"answer\":",
+            self.year, self.day, self.part, json_string(&self.variant), json_string(&self.status)
+        );
+        match &self.answer {
+            Some(answer) => json.push_str(&json_string(answer)),
+            None => json.push_str("null"),
+        }
+        let number = |n: Option<u128>| n.map_or_else(|| "null".to_string(), |n| n.to_string());
+        let _ = write!(
+            json,
+            ",\"parse_ns\":{},\"solve_ns\":{},\"peak_memory_kb\":{},\"allocations\":{},\"allocated_bytes\":{},\"peak_heap_bytes\":{}}}",
+            number(self.parse.map(|d| d.as_nanos())),
+            number(self.solve.map(|d| d.as_nanos())),
+            number(self.peak_memory.map(u128::from)),
+            number(self.allocations.map(|a| a.count.into())),
+            number(self.allocations.map(|a| a.bytes.into())),
+            number(self.allocations.map(|a| a.peak_heap.into())),
+        );
+        json
+    }
+
+    /// A line matching `CSV_HEADER`, missing values are left empty.
+    pub fn to_csv(&self) -> String {
+        let number = |n: Option<u128>| n.map_or_else(String::new, |n| n.to_string());
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.year,
+            self.day,
+            self.part,
+            csv_field(&self.variant),
+            csv_field(&self.status),
+            self.answer.as_deref().map_or_else(String::new, csv_field),
+            number(self.parse.map(|d| d.as_nanos())),
+            number(self.solve.map(|d| d.as_nanos())),
+            number(self.peak_memory.map(u128::from)),
+            number(self.allocations.map(|a| a.count.into())),
+            number(self.allocations.map(|a| a.bytes.into())),
+            number(self.allocations.map(|a| a.peak_heap.into())),
+        )
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> Record {
+        Record {
+            year: 2022,
+            day: 10,
+            part: Part::Two,
+            variant: "default".to_string(),
+            status: "ok".to_string(),
+            answer: Some("#.\n.#".to_string()),
+            parse: Some(Duration::from_micros(2)),
+            solve: Some(Duration::from_nanos(500)),
+            peak_memory: None,
+            allocations: Some(Allocations { count: 3, bytes: 96, peak_heap: 1024 }),
+        }
+    }
+
+    #[test]
+    fn to_json_works() {
+        assert_eq!(
+            r##"{"year":2022,"day":10,"part":2,"variant":"default","status":"ok","answer":"#.\n.#","parse_ns":2000,"solve_ns":500,"peak_memory_kb":null,"allocations":3,"allocated_bytes":96,"peak_heap_bytes":1024}"##,
+            record().to_json()
+        );
+        assert_eq!(r#""say \"hi\"\\\u0001""#, json_string("say \"hi\"\\\u{1}"));
+    }
+
+    #[test]
+    fn to_csv_works() {
+        assert_eq!("2022,10,2,default,ok,\"#.\n.#\",2000,500,,3,96,1024", record().to_csv());
+        assert_eq!("\"failed: a, \"\"b\"\"\"", csv_field("failed: a, \"b\""));
+    }
+}
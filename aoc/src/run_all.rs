@@ -0,0 +1,283 @@
+use std::{
+    fmt::Display,
+    io,
+    path::Path,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use aoc_core::{tracing::info_span, Answer, Error, Part, Variant};
+use rayon::prelude::*;
+
+use crate::{
+    answers::{self, Expected},
+    bench::{self, Outcome},
+    days::Day,
+    inputs::Inputs,
+    memory::{self, Allocations},
+    report::Record,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// The answer matches the recorded one.
+    Correct,
+    Wrong,
+    /// There is no recorded answer to compare with.
+    New,
+    Unimplemented,
+    Failed(String),
+    TimedOut,
+    /// The part before timed out.
+    Skipped,
+}
+
+impl Status {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Wrong | Status::Failed(_) | Status::TimedOut)
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Correct => f.write_str("ok"),
+            Status::Wrong => f.write_str("WRONG"),
+            Status::New => f.write_str("new"),
+            Status::Unimplemented => f.write_str("unimplemented"),
+            Status::Failed(err) => write!(f, "failed: {err}"),
+            Status::TimedOut => f.write_str("timed out"),
+            Status::Skipped => f.write_str("-"),
+        }
+    }
+}
+
+pub struct Row {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub answer: Option<Answer>,
+    pub parse: Option<Duration>,
+    pub solve: Option<Duration>,
+    pub status: Status,
+    /// Peak resident set size of the whole day in KiB, only known when days run one by one.
+    pub peak_memory: Option<u64>,
+    /// What the part allocated, with the `count-allocations` feature and days run one by one.
+    pub allocations: Option<Allocations>,
+}
+
+impl Row {
+    pub fn record(&self) -> Record {
+        Record {
+            year: self.year,
+            day: self.day,
+            part: self.part,
+            variant: Variant::DEFAULT.to_string(),
+            status: self.status.to_string(),
+            answer: self.answer.as_ref().map(Answer::to_string),
+            parse: self.parse,
+            solve: self.solve,
+            peak_memory: self.peak_memory,
+            allocations: self.allocations,
+        }
+    }
+}
+
+/// Solves both parts of every day once, `jobs` days at a time, and compares the answers
+/// with the ones recorded below `root`. Rows are in the order of `days`.
+///
+/// A stage that runs longer than `timeout` is given up and left behind on its thread, like
+/// in `bench::run`, so the other days carry on. The peak memory of each day is only measured
+/// with `jobs` set to 1, otherwise the days' allocations mix.
+pub fn run(days: &[&Day], inputs: &Inputs, root: &Path, timeout: Duration, jobs: Option<usize>) -> io::Result<Vec<Row>> {
+    let mut expected: Vec<(u16, Vec<Expected>)> = Vec::new();
+    for year in days.iter().map(|d| d.year) {
+        if expected.iter().all(|(y, _)| *y != year) {
+            let answers = match answers::load(root, year) {
+                Ok(answers) => answers,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+                Err(err) => return Err(err),
+            };
+            expected.push((year, answers));
+        }
+    }
+
+    // downloads go through one client that spaces them out, so inputs are fetched up front
+    let day_inputs: Vec<_> = days.iter().map(|day| (*day, inputs.get(day.year, day.day))).collect();
+
+    let mut builder = rayon::ThreadPoolBuilder::new();
+    if let Some(jobs) = jobs {
+        builder = builder.num_threads(jobs);
+    }
+    let track_memory = jobs == Some(1);
+    let pool = builder.build().map_err(io::Error::other)?;
+    let rows = pool.install(|| {
+        day_inputs.into_par_iter()
+            .map(|(day, input)| {
+                let year_answers = expected.iter()
+                    .find(|(year, _)| *year == day.year)
+                    .map_or(&[][..], |(_, answers)| answers);
+                match input {
+                    Ok(input) if track_memory => {
+                        memory::reset_peak();
+                        let mut rows = run_day(day, input, year_answers, timeout);
+                        let peak = memory::peak_kb();
+                        rows.iter_mut().for_each(|row| row.peak_memory = peak);
+                        rows
+                    }
+                    Ok(input) => {
+                        // the allocation counters are shared, so they mix the days running at once
+                        let mut rows = run_day(day, input, year_answers, timeout);
+                        rows.iter_mut().for_each(|row| row.allocations = None);
+                        rows
+                    }
+                    Err(err) => failed(day, Status::Failed(err)),
+                }
+            })
+            .collect::<Vec<Vec<Row>>>()
+    });
+    Ok(rows.into_iter().flatten().collect())
+}
+
+fn row(day: &Day, part: Part, answer: Option<Answer>, parse: Option<Duration>, solve: Option<Duration>, status: Status) -> Row {
+    Row { year: day.year, day: day.day, part, answer, parse, solve, status, peak_memory: None, allocations: None }
+}
+
+/// Runs one day on its own thread and waits for each stage at most `timeout`.
+fn run_day(day: &Day, input: String, expected: &[Expected], timeout: Duration) -> Vec<Row> {
+    let settings = bench::Settings { runs: 1, budget: Duration::ZERO, timeout, variants: false };
+    let (sender, receiver) = mpsc::channel();
+    let measure = day.measure;
+    let span = info_span!("solve", year = day.year, day = day.day);
+    thread::spawn(move || span.in_scope(|| measure(&input, &settings, &sender)));
+
+    let receive = || match receiver.recv_timeout(timeout) {
+        Ok(outcome) => outcome,
+        Err(RecvTimeoutError::Timeout) => Outcome::TimedOut,
+        Err(RecvTimeoutError::Disconnected) => Outcome::Failed(Error::NoSolution("the solution panicked".to_string())),
+    };
+
+    let parse = match receive() {
+        Outcome::Timed(stats) => stats.median,
+        Outcome::Failed(err) => return failed(day, Status::Failed(err.to_string())),
+        _ => return failed(day, Status::TimedOut),
+    };
+    let mut rows = Vec::new();
+    let mut timed_out = false;
+    for part in Part::ALL {
+        if timed_out {
+            rows.push(row(day, part, None, Some(parse), None, Status::Skipped));
+            continue;
+        }
+        let mut allocations = None;
+        let (answer, solve, status) = match receive() {
+            Outcome::Answered(stats, answer) => {
+                allocations = stats.allocations;
+                let status = match expected.iter().find(|e| e.day == day.day && e.part == part) {
+                    Some(e) if e.answer == answer.to_string() => Status::Correct,
+                    Some(_) => Status::Wrong,
+                    None => Status::New,
+                };
+                (Some(answer), Some(stats.median), status)
+            }
+            Outcome::Failed(Error::Unimplemented) => (None, None, Status::Unimplemented),
+            Outcome::Failed(err) => (None, None, Status::Failed(err.to_string())),
+            _ => {
+                timed_out = true;
+                (None, None, Status::TimedOut)
+            }
+        };
+        rows.push(Row { allocations, ..row(day, part, answer, Some(parse), solve, status) });
+    }
+    rows
+}
+
+/// Rows for a day that did not get as far as solving.
+fn failed(day: &Day, status: Status) -> Vec<Row> {
+    Part::ALL.iter().map(|&part| row(day, part, None, None, None, status.clone())).collect()
+}
+
+pub fn print_table(rows: &[Row]) {
+    let heap = if memory::COUNTING { format!(" {:>12}", "peak heap") } else { String::new() };
+    println!("{:<12} {:<20} {:>12} {:>12}{heap}  status", "part", "answer", "parse", "solve");
+    for Row { year, day, part, answer, parse, solve, status, allocations, .. } in rows {
+        let label = format!("{year} {day:02} part{part}");
+        let answer = match answer {
+            Some(Answer::Grid(_)) => "(grid)".to_string(),
+            Some(answer) => answer.to_string(),
+            None => String::new(),
+        };
+        let duration = |d: &Option<Duration>| d.map_or_else(|| "-".to_string(), |d| format!("{d:.1?}"));
+        let heap = match allocations {
+            Some(allocations) => format!(" {:>12}", memory::format_bytes(allocations.peak_heap)),
+            None if memory::COUNTING => format!(" {:>12}", "-"),
+            None => String::new(),
+        };
+        println!("{label:<12} {answer:<20} {:>12} {:>12}{heap}  {status}", duration(parse), duration(solve));
+    }
+
+    let count = |f: fn(&Status) -> bool| rows.iter().filter(|row| f(&row.status)).count();
+    println!(
+        "{} parts: {} ok, {} new, {} unimplemented, {} failed",
+        rows.len(),
+        count(|s| *s == Status::Correct),
+        count(|s| *s == Status::New),
+        count(|s| *s == Status::Unimplemented),
+        count(Status::is_failure),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::mpsc::Sender, time::Instant};
+
+    use aoc_core::{Params, Result};
+
+    use super::*;
+
+    fn solve(_: &str, _: Part, _: &str, _: &Params) -> Result<Answer> {
+        unreachable!("run_all only measures")
+    }
+
+    fn timed(_: &str, _: Part, _: &str, _: &Params) -> Result<bench::Timed> {
+        unreachable!("run_all only measures")
+    }
+
+    fn quick(input: &str, _: &bench::Settings, outcomes: &Sender<Outcome>) {
+        let stats = || bench::Stats { runs: 1, median: Duration::ZERO, min: Duration::ZERO, max: Duration::ZERO, allocations: None };
+        let _ = outcomes.send(Outcome::Timed(stats()));
+        let _ = outcomes.send(Outcome::Answered(stats(), Answer::Int(input.len() as i64)));
+        let _ = outcomes.send(Outcome::Failed(Error::Unimplemented));
+    }
+
+    fn stuck(_: &str, _: &bench::Settings, outcomes: &Sender<Outcome>) {
+        let stats = bench::Stats { runs: 1, median: Duration::ZERO, min: Duration::ZERO, max: Duration::ZERO, allocations: None };
+        let _ = outcomes.send(Outcome::Timed(stats));
+        thread::sleep(Duration::from_secs(60));
+    }
+
+    const QUICK: Day = Day { year: 2000, day: 1, solve, timed, params: &[], variants: &[], measure: quick };
+    const STUCK: Day = Day { year: 2000, day: 2, solve, timed, params: &[], variants: &[], measure: stuck };
+
+    #[test]
+    fn run_day_compares_answers() {
+        let expected = [Expected { day: 1, part: Part::One, answer: "3".to_string() }];
+        let rows = run_day(&QUICK, "abc".to_string(), &expected, Duration::from_secs(1));
+        let statuses: Vec<_> = rows.iter().map(|row| row.status.clone()).collect();
+        assert_eq!(vec![Status::Correct, Status::Unimplemented], statuses);
+
+        let rows = run_day(&QUICK, "ab".to_string(), &expected, Duration::from_secs(1));
+        assert_eq!(Status::Wrong, rows[0].status);
+    }
+
+    #[test]
+    fn run_day_gives_up_after_timeout() {
+        let start = Instant::now();
+        let rows = run_day(&STUCK, String::new(), &[], Duration::from_millis(50));
+        assert!(start.elapsed() < Duration::from_secs(5));
+        let statuses: Vec<_> = rows.iter().map(|row| row.status.clone()).collect();
+        assert_eq!(vec![Status::TimedOut, Status::Skipped], statuses);
+        assert!(rows[0].parse.is_some());
+    }
+}
@@ -0,0 +1,192 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+const TEMPLATE: &[(&str, &str)] = &[
+    ("Cargo.toml", include_str!("../template/Cargo.toml")),
+    ("src/lib.rs", include_str!("../template/src/lib.rs")),
+    ("src/parser.rs", include_str!("../template/src/parser.rs")),
+    ("src/types.rs", include_str!("../template/src/types.rs")),
+    ("fixtures/example.txt", include_str!("../template/fixtures/example.txt")),
+];
+
+const ANSWERS: &str = "\
+# Known answers for the YEAR puzzle inputs, checked by `aoc verify`.
+# One answer per line: <day> <part> <answer>. Grid answers are left empty on that line and
+# follow on the next lines, indented by two spaces.
+";
+
+/// Creates the crate for a new day below `root` from the template, adds it to the
+/// workspace and registers it in the runner. The first day of a year also gets an empty
+/// `answers.txt`. Nothing is written when the day exists already.
+pub fn create(root: &Path, year: u16, day: u8) -> Result<PathBuf, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("invalid day {day}, expected 1 to 25"));
+    }
+    let dir_name = format!("day-{day:02}");
+    let dir = root.join(year.to_string()).join(&dir_name);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+    let package = format!("aoc-{year}-day-{day:02}");
+    let solution = format!("Day{day:02}");
+    let solution_path = format!("{}::{solution}", package.replace('-', "_"));
+
+    let workspace_path = root.join("Cargo.toml");
+    let runner_path = root.join("aoc/Cargo.toml");
+    let days_path = root.join("aoc/src/days.rs");
+    let workspace = add_member(&read(&workspace_path)?, &format!("{year}/day-*"));
+    let runner = add_dependency(&read(&runner_path)?, &package, &format!("../{year}/{dir_name}"))?;
+    let days = register_day(&read(&days_path)?, year, day, &solution_path)?;
+
+    for (file, template) in TEMPLATE {
+        let contents = template
+            .replace("DAY_TEMPLATE_PACKAGE", &package)
+            .replace("DAY_TEMPLATE_STRUCT", &solution);
+        write_new(&dir.join(file), &contents)?;
+    }
+    let answers_path = root.join(year.to_string()).join("answers.txt");
+    if !answers_path.exists() {
+        write_new(&answers_path, &ANSWERS.replace("YEAR", &year.to_string()))?;
+    }
+    write(&workspace_path, &workspace)?;
+    write(&runner_path, &runner)?;
+    write(&days_path, &days)?;
+    Ok(dir)
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("could not read {}: {err}", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|err| format!("could not write {}: {err}", path.display()))
+}
+
+/// Like `write`, but fails instead of overwriting an existing file.
+fn write_new(path: &Path, contents: &str) -> Result<(), String> {
+    let err = |err| format!("could not create {}: {err}", path.display());
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(err)?;
+    }
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path).map_err(err)?;
+    file.write_all(contents.as_bytes()).map_err(err)
+}
+
+/// Adds `member` to the `members` list of the workspace manifest, unless it is there already.
+fn add_member(manifest: &str, member: &str) -> String {
+    let quoted = format!("\"{member}\"");
+    if manifest.contains(&quoted) {
+        return manifest.to_string();
+    }
+    let mut lines: Vec<String> = manifest.lines().map(str::to_string).collect();
+    let start = lines.iter().position(|line| line.trim_start().starts_with("members"));
+    let end = start.and_then(|start| lines[start..].iter().position(|line| line.trim() == "]").map(|end| start + end));
+    match end {
+        Some(end) => lines.insert(end, format!("    {quoted},")),
+        None => lines.push(format!("members = [{quoted}]")),
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Adds a path dependency on `package` at the end of the runner's `[dependencies]`.
+fn add_dependency(manifest: &str, package: &str, path: &str) -> Result<String, String> {
+    if manifest.lines().any(|line| line.starts_with(&format!("{package} "))) {
+        return Err(format!("the runner already depends on {package}"));
+    }
+    let mut lines: Vec<String> = manifest.lines().map(str::to_string).collect();
+    let start = lines.iter().position(|line| line.trim() == "[dependencies]")
+        .ok_or("the runner manifest has no [dependencies]")?;
+    let end = lines[start + 1..].iter()
+        .position(|line| line.starts_with('['))
+        .map_or(lines.len(), |end| start + 1 + end);
+    let last = lines[start..end].iter().rposition(|line| !line.trim().is_empty()).map_or(start, |last| start + last);
+    lines.insert(last + 1, format!("{package} = {{ path = \"{path}\" }}"));
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds a `day!` entry to `DAYS`, keeping it ordered by year and day.
+fn register_day(source: &str, year: u16, day: u8, solution: &str) -> Result<String, String> {
+    let mut lines: Vec<String> = source.lines().map(str::to_string).collect();
+    let start = lines.iter().position(|line| line.starts_with("pub const DAYS"))
+        .ok_or("days.rs has no DAYS table")?;
+    let mut insert_at = start + 1;
+    for (idx, line) in lines.iter().enumerate().skip(start + 1) {
+        let Some(entry) = line.trim().strip_prefix("day!(") else {
+            break;
+        };
+        let mut fields = entry.split(',').map(str::trim);
+        let key = (fields.next().and_then(|y| y.parse::<u16>().ok()), fields.next().and_then(|d| d.parse::<u8>().ok()));
+        match key {
+            (Some(y), Some(d)) if (y, d) == (year, day) => return Err(format!("{year} day {day} is already registered")),
+            (Some(y), Some(d)) if (y, d) > (year, day) => break,
+            _ => insert_at = idx + 1,
+        }
+    }
+    lines.insert(insert_at, format!("    day!({year}, {day}, {solution}),"));
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    const DAYS: &str = "pub const DAYS: &[Day] = &[\n    day!(2022, 1, aoc_2022_day_01::Day01),\n    day!(2022, 3, aoc_2022_day_03::Day03),\n];\n";
+
+    #[test]
+    fn add_member_works() {
+        let manifest = "[workspace]\nmembers = [\n    \"aoc\",\n    \"2022/day-*\",\n]\n";
+        assert_eq!(manifest, add_member(manifest, "2022/day-*"));
+        assert_eq!(
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"2022/day-*\",\n    \"2023/day-*\",\n]\n",
+            add_member(manifest, "2023/day-*")
+        );
+    }
+
+    #[test]
+    fn add_dependency_works() {
+        let manifest = "[package]\nname = \"aoc\"\n\n[dependencies]\naoc-2022-day-01 = { path = \"../2022/day-01\" }\n";
+        assert_eq!(
+            Ok(format!("{manifest}aoc-2023-day-01 = {{ path = \"../2023/day-01\" }}\n")),
+            add_dependency(manifest, "aoc-2023-day-01", "../2023/day-01")
+        );
+        assert!(add_dependency(manifest, "aoc-2022-day-01", "../2022/day-01").is_err());
+    }
+
+    #[test]
+    fn register_day_works() {
+        let days = register_day(DAYS, 2022, 2, "aoc_2022_day_02::Day02").unwrap();
+        assert_eq!("    day!(2022, 2, aoc_2022_day_02::Day02),", days.lines().nth(2).unwrap());
+        let days = register_day(DAYS, 2022, 25, "aoc_2022_day_25::Day25").unwrap();
+        assert_eq!("    day!(2022, 25, aoc_2022_day_25::Day25),", days.lines().nth(3).unwrap());
+        assert_eq!(Err("2022 day 3 is already registered".to_string()), register_day(DAYS, 2022, 3, "aoc_2022_day_03::Day03"));
+    }
+
+    #[test]
+    fn create_works() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\n    \"aoc\",\n]\n").unwrap();
+        fs::write(root.join("aoc/Cargo.toml"), "[package]\nname = \"aoc\"\n\n[dependencies]\n").unwrap();
+        fs::write(root.join("aoc/src/days.rs"), DAYS).unwrap();
+
+        create(&root, 2022, 2).unwrap();
+        let lib = fs::read_to_string(root.join("2022/day-02/src/lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day02;"));
+        assert!(root.join("2022/day-02/src/parser.rs").exists());
+        assert!(root.join("2022/day-02/fixtures/example.txt").exists());
+        assert!(fs::read_to_string(root.join("Cargo.toml")).unwrap().contains("\"2022/day-*\""));
+        assert!(fs::read_to_string(root.join("aoc/Cargo.toml")).unwrap().contains("aoc-2022-day-02 = { path = \"../2022/day-02\" }"));
+        assert!(fs::read_to_string(root.join("aoc/src/days.rs")).unwrap().contains("day!(2022, 2, aoc_2022_day_02::Day02)"));
+
+        assert!(root.join("2022/answers.txt").exists());
+
+        let err = create(&root, 2022, 2).unwrap_err();
+        assert!(err.ends_with("already exists"), "{err}");
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    cell::Cell,
+    env, fs,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::workspace_root;
+
+const USER_AGENT: &str = concat!("aoc-runner/", env!("CARGO_PKG_VERSION"), " (personal puzzle runner)");
+
+pub struct Config {
+    /// Where the site lives, only changed to point the client at a test server.
+    pub base_url: String,
+    /// Value of the `session` cookie of a logged in browser.
+    pub session: Option<String>,
+    /// Shortest time between two requests.
+    pub min_interval: Duration,
+}
+
+impl Config {
+    /// Reads `AOC_BASE_URL` and `AOC_SESSION`. Without `AOC_SESSION` the token is read from a
+    /// `.session` file in the workspace root.
+    pub fn from_env() -> Self {
+        let session = env::var("AOC_SESSION").ok()
+            .or_else(|| fs::read_to_string(workspace_root().join(".session")).ok())
+            .map(|session| session.trim().to_string())
+            .filter(|session| !session.is_empty());
+        Config {
+            base_url: env::var("AOC_BASE_URL").unwrap_or_else(|_| "https://adventofcode.com".to_string()),
+            session,
+            min_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+/// A client for adventofcode.com that keeps its requests apart.
+pub struct Site {
+    config: Config,
+    agent: ureq::Agent,
+    last_request: Cell<Option<Instant>>,
+}
+
+impl Site {
+    pub fn new(config: Config) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+        Site { config, agent, last_request: Cell::new(None) }
+    }
+
+    pub fn get(&self, path: &str) -> Result<String, SiteError> {
+        let request = self.request("GET", path)?;
+        let response = request.call().map_err(SiteError::from)?;
+        response.into_string().map_err(|err| SiteError::Failed(err.to_string()))
+    }
+
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String, SiteError> {
+        let request = self.request("POST", path)?;
+        let response = request.send_form(form).map_err(SiteError::from)?;
+        response.into_string().map_err(|err| SiteError::Failed(err.to_string()))
+    }
+
+    fn request(&self, method: &str, path: &str) -> Result<ureq::Request, SiteError> {
+        let session = self.config.session.as_deref().ok_or(SiteError::NoSession)?;
+        if let Some(last) = self.last_request.get() {
+            thread::sleep(self.config.min_interval.saturating_sub(last.elapsed()));
+        }
+        self.last_request.set(Some(Instant::now()));
+
+        let url = format!("{}{path}", self.config.base_url.trim_end_matches('/'));
+        Ok(self.agent.request(method, &url).set("Cookie", &format!("session={session}")))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SiteError {
+    NoSession,
+    /// The site answered with an error status.
+    Status(u16),
+    Failed(String),
+}
+
+impl From<ureq::Error> for SiteError {
+    fn from(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Status(400 | 401 | 403, _) => SiteError::Failed(
+                "the session token was rejected, it may have expired".to_string()
+            ),
+            ureq::Error::Status(status, _) => SiteError::Status(status),
+            ureq::Error::Transport(err) => SiteError::Failed(err.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for SiteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SiteError::NoSession => write!(
+                f,
+                "no session token, set AOC_SESSION or put the `session` cookie of adventofcode.com into {}",
+                workspace_root().join(".session").display()
+            ),
+            SiteError::Status(status) => write!(f, "the site answered with status {status}"),
+            SiteError::Failed(reason) => f.write_str(reason),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+    };
+
+    use super::*;
+
+    /// A request as the stub server saw it.
+    pub struct Request {
+        pub head: Vec<String>,
+        pub body: String,
+    }
+
+    /// Serves `responses` one connection each and sends the requests it got back.
+    pub fn stub_server(responses: Vec<String>) -> (String, mpsc::Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(&stream);
+                let head: Vec<String> = reader.by_ref().lines()
+                    .map(Result::unwrap)
+                    .take_while(|line| !line.is_empty())
+                    .collect();
+                let length = head.iter()
+                    .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length: ").map(str::to_string))
+                    .map_or(0, |length| length.parse().unwrap());
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+                sender.send(Request { head, body: String::from_utf8(body).unwrap() }).unwrap();
+            }
+        });
+        (base_url, receiver)
+    }
+
+    pub fn ok(body: &str) -> String {
+        format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}", body.len())
+    }
+
+    pub fn site(base_url: String) -> Site {
+        Site::new(Config { base_url, session: Some("abc".to_string()), min_interval: Duration::ZERO })
+    }
+
+    #[test]
+    fn requests_carry_the_session() {
+        let (base_url, requests) = stub_server(vec![ok("hi")]);
+        assert_eq!(Ok("hi".to_string()), site(base_url).get("/2022/day/1/input"));
+        let request = requests.recv().unwrap();
+        assert_eq!("GET /2022/day/1/input HTTP/1.1", request.head[0]);
+        assert!(request.head.iter().any(|line| line.eq_ignore_ascii_case("cookie: session=abc")), "{:?}", request.head);
+    }
+
+    #[test]
+    fn requests_are_spaced_out() {
+        let (base_url, _requests) = stub_server(vec![ok("1"), ok("2")]);
+        let site = Site::new(Config {
+            base_url,
+            session: Some("abc".to_string()),
+            min_interval: Duration::from_millis(200),
+        });
+        let start = Instant::now();
+        site.get("/a").unwrap();
+        site.get("/b").unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn missing_session_is_reported() {
+        let site = Site::new(Config { base_url: "http://127.0.0.1:9".to_string(), session: None, min_interval: Duration::ZERO });
+        assert_eq!(Err(SiteError::NoSession), site.get("/"));
+    }
+}
@@ -0,0 +1,286 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aoc_core::Part;
+
+use crate::{
+    answers::{self, Expected, Rejected, Rejection},
+    site::Site,
+};
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong(Rejection),
+    /// The previous answer was too recent, this one was not looked at.
+    TooSoon,
+    /// The part was solved before, the site does not check answers to it anymore.
+    AlreadySolved,
+    /// A page the client does not understand, as text.
+    Unknown(String),
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Right => f.write_str("right answer"),
+            Verdict::Wrong(Rejection::Wrong) => f.write_str("wrong answer"),
+            Verdict::Wrong(Rejection::TooHigh) => f.write_str("wrong answer, too high"),
+            Verdict::Wrong(Rejection::TooLow) => f.write_str("wrong answer, too low"),
+            Verdict::TooSoon => f.write_str("answered too soon after the previous answer"),
+            Verdict::AlreadySolved => f.write_str("this part is solved already"),
+            Verdict::Unknown(text) => write!(f, "unexpected response: {text}"),
+        }
+    }
+}
+
+pub struct Submission {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+}
+
+/// Submits an answer, unless the registry below `root` already tells how it would go, and
+/// records the verdict there. The wait the site asks for afterwards is kept in
+/// `<root>/.submit-wait` and honored by the next submission.
+pub fn submit(site: &Site, root: &Path, submission: &Submission) -> Result<Verdict, String> {
+    let Submission { year, day, part, answer } = submission;
+    let throttle_path = root.join(".submit-wait");
+    if let Some(left) = wait_left(&throttle_path) {
+        return Err(format!("the site asked to wait before answering again, {}s to go", left.as_secs()));
+    }
+
+    let answers = match answers::load(root, *year) {
+        Ok(answers) => answers,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(err) => return Err(format!("could not load {}: {err}", answers::path(root, *year).display())),
+    };
+    let rejected = answers::load_rejected(root, *year)
+        .map_err(|err| format!("could not load {}: {err}", answers::rejected_path(root, *year).display()))?;
+    check(answer, *day, *part, &answers, &rejected)?;
+
+    let level = part.to_string();
+    let page = site.post_form(&format!("/{year}/day/{day}/answer"), &[("level", &level), ("answer", answer)])
+        .map_err(|err| format!("could not submit the answer: {err}"))?;
+    let (verdict, wait) = parse_response(&page);
+
+    if let Some(wait) = wait {
+        record_wait(&throttle_path, wait)
+            .map_err(|err| format!("could not write {}: {err}", throttle_path.display()))?;
+    }
+    match verdict {
+        Verdict::Right => {
+            let expected = Expected { day: *day, part: *part, answer: answer.clone() };
+            answers::record(root, *year, &expected)
+                .map_err(|err| format!("could not record the answer in {}: {err}", answers::path(root, *year).display()))?;
+        }
+        Verdict::Wrong(rejection) => {
+            let rejected = Rejected { day: *day, part: *part, answer: answer.clone(), rejection };
+            answers::record_rejected(root, *year, &rejected)
+                .map_err(|err| format!("could not record the answer in {}: {err}", answers::rejected_path(root, *year).display()))?;
+        }
+        _ => {}
+    }
+    Ok(verdict)
+}
+
+/// Refuses answers the site cannot take and answers the registry already has a verdict for.
+fn check(answer: &str, day: u8, part: Part, answers: &[Expected], rejected: &[Rejected]) -> Result<(), String> {
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Err("only single-line answers can be submitted, read grid answers off and pass them with --answer".to_string());
+    }
+    if let Some(expected) = answers.iter().find(|e| e.day == day && e.part == part) {
+        return Err(if expected.answer == answer {
+            format!("{answer} is the recorded answer already")
+        } else {
+            format!("part {part} is solved already with {}, not {answer}", expected.answer)
+        });
+    }
+
+    let earlier_answers: Vec<_> = rejected.iter().filter(|r| r.day == day && r.part == part).collect();
+    if let Some(earlier) = earlier_answers.iter().find(|r| r.answer == answer) {
+        return Err(format!("{answer} was rejected before ({})", earlier.rejection));
+    }
+    let number = answer.parse::<i64>().ok();
+    for earlier in earlier_answers {
+        let bound = earlier.answer.parse::<i64>().ok();
+        match (earlier.rejection, number, bound) {
+            (Rejection::TooHigh, Some(number), Some(bound)) if number >= bound =>
+                return Err(format!("{answer} can not be right, {bound} was too high already")),
+            (Rejection::TooLow, Some(number), Some(bound)) if number <= bound =>
+                return Err(format!("{answer} can not be right, {bound} was too low already")),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Reads the verdict and the time to wait before the next answer from the page the site
+/// sends back.
+fn parse_response(page: &str) -> (Verdict, Option<Duration>) {
+    let text = article_text(page);
+    let verdict = if text.contains("That's the right answer") {
+        Verdict::Right
+    } else if text.contains("That's not the right answer") {
+        Verdict::Wrong(if text.contains("answer is too high") {
+            Rejection::TooHigh
+        } else if text.contains("answer is too low") {
+            Rejection::TooLow
+        } else {
+            Rejection::Wrong
+        })
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::TooSoon
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unknown(text.clone())
+    };
+    (verdict, parse_wait(&text))
+}
+
+/// The text of the page's `<article>`, or of the whole page when it has none.
+fn article_text(page: &str) -> String {
+    let article = page.split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article.split_once('>').map_or(article, |(_, content)| content));
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Understands "You have 4m 32s left to wait" and "please wait one minute before trying again".
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some((before, _)) = text.split_once(" left to wait") {
+        let (_, amount) = before.rsplit_once("You have ")?;
+        let seconds = amount.split_whitespace()
+            .map(|part| {
+                let (number, unit) = part.split_at(part.len().checked_sub(1)?);
+                let number = number.parse::<u64>().ok()?;
+                match unit {
+                    "h" => Some(number * 3600),
+                    "m" => Some(number * 60),
+                    "s" => Some(number),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()?;
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let (_, rest) = text.split_once("wait ")?;
+    let (amount, _) = rest.split_once(" minute")?;
+    let minutes = match amount {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "ten" => 10,
+        amount => amount.parse().ok()?,
+    };
+    Some(Duration::from_secs(minutes * 60))
+}
+
+fn now() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).expect("clock is after 1970")
+}
+
+/// How long the last response asked to wait still, if at all.
+fn wait_left(path: &Path) -> Option<Duration> {
+    let until = fs::read_to_string(path).ok()?.trim().parse::<u64>().ok()?;
+    Duration::from_secs(until).checked_sub(now()).filter(|left| !left.is_zero())
+}
+
+fn record_wait(path: &Path, wait: Duration) -> io::Result<()> {
+    fs::write(path, format!("{}\n", (now() + wait).as_secs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use crate::site::tests::{ok, site, stub_server};
+
+    use super::*;
+
+    const RIGHT: &str = "<html><main><article><p>That's the right answer! You are <em>one gold star</em> closer \
+        to saving Christmas.</p></article></main></html>";
+    const TOO_HIGH: &str = "<main>\n<article><p>That's not the right answer; your answer is too high.  If you're \
+        stuck, make sure you're using the full input data. Please wait one minute before trying again. \
+        <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>\n</main>";
+    const TOO_SOON: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an \
+        answer before trying again.  You have 4m 32s left to wait. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>";
+    const SOLVED: &str = "<article><p>You don't seem to be solving the right level.  Did you already complete it? \
+        <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>";
+
+    fn expected(day: u8, part: Part, answer: &str) -> Expected {
+        Expected { day, part, answer: answer.to_string() }
+    }
+
+    fn rejected(answer: &str, rejection: Rejection) -> Rejected {
+        Rejected { day: 1, part: Part::One, answer: answer.to_string(), rejection }
+    }
+
+    #[test]
+    fn parse_response_works() {
+        assert_eq!((Verdict::Right, None), parse_response(RIGHT));
+        assert_eq!((Verdict::Wrong(Rejection::TooHigh), Some(Duration::from_secs(60))), parse_response(TOO_HIGH));
+        assert_eq!((Verdict::TooSoon, Some(Duration::from_secs(272))), parse_response(TOO_SOON));
+        assert_eq!((Verdict::AlreadySolved, None), parse_response(SOLVED));
+        assert_eq!(Verdict::Unknown("Oops".to_string()), parse_response("<p>Oops</p>").0);
+    }
+
+    #[test]
+    fn check_works() {
+        let answers = [expected(1, Part::Two, "45000")];
+        let rejected = [rejected("500", Rejection::TooHigh), rejected("100", Rejection::TooLow), rejected("42", Rejection::Wrong)];
+        assert_eq!(Ok(()), check("300", 1, Part::One, &answers, &rejected));
+        assert_eq!(Err("42 was rejected before (wrong)".to_string()), check("42", 1, Part::One, &answers, &rejected));
+        assert_eq!(Err("600 can not be right, 500 was too high already".to_string()), check("600", 1, Part::One, &answers, &rejected));
+        assert_eq!(Err("99 can not be right, 100 was too low already".to_string()), check("99", 1, Part::One, &answers, &rejected));
+        assert_eq!(Err("45000 is the recorded answer already".to_string()), check("45000", 1, Part::Two, &answers, &rejected));
+        assert!(check("#..#\n.##.", 1, Part::One, &answers, &rejected).is_err());
+    }
+
+    #[test]
+    fn submit_records_verdicts() {
+        let root = env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("2022")).unwrap();
+        let (base_url, requests) = stub_server(vec![ok(TOO_HIGH), ok(RIGHT)]);
+        let site = site(base_url);
+        let submission = |answer: &str| Submission { year: 2022, day: 1, part: Part::One, answer: answer.to_string() };
+
+        assert_eq!(Ok(Verdict::Wrong(Rejection::TooHigh)), submit(&site, &root, &submission("700")));
+        let request = requests.recv().unwrap();
+        assert_eq!("POST /2022/day/1/answer HTTP/1.1", request.head[0]);
+        assert_eq!("level=1&answer=700", request.body);
+        assert_eq!("1 1 700 too-high\n", fs::read_to_string(root.join("2022/rejected.txt")).unwrap());
+
+        // the site asked for a minute, nothing is sent before
+        let err = submit(&site, &root, &submission("600")).unwrap_err();
+        assert!(err.contains("asked to wait"), "{err}");
+        fs::remove_file(root.join(".submit-wait")).unwrap();
+
+        assert!(submit(&site, &root, &submission("800")).unwrap_err().contains("too high already"));
+        assert_eq!(Ok(Verdict::Right), submit(&site, &root, &submission("600")));
+        assert_eq!("1 1 600\n", fs::read_to_string(root.join("2022/answers.txt")).unwrap());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
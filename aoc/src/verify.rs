@@ -0,0 +1,65 @@
+use std::{io, time::Instant};
+
+use aoc_core::{tracing::info_span, Params, Variant};
+
+use crate::{answers, days, inputs::Inputs, workspace_root};
+
+/// Solves every part with a recorded answer for `year` (optionally only `day`) on its real input,
+/// with every variant of the part, and prints how it compares. Returns whether all answers matched.
+pub fn run(year: u16, day: Option<u8>, inputs: &Inputs) -> io::Result<bool> {
+    let answers = answers::load(workspace_root(), year)?;
+    let mut all_correct = true;
+    for expected in answers.iter().filter(|e| day.is_none_or(|day| e.day == day)) {
+        let label = format!("{year} {:02} part{}", expected.day, expected.part);
+        let Some(solution) = days::find(year, expected.day) else {
+            println!("{label}: no solution registered");
+            all_correct = false;
+            continue;
+        };
+        let input = match inputs.get(year, expected.day) {
+            Ok(input) => input,
+            Err(err) => {
+                println!("{label}: {err}");
+                all_correct = false;
+                continue;
+            }
+        };
+
+        for variant in solution.variants_of(expected.part) {
+            let label = match variant {
+                Variant::DEFAULT => label.clone(),
+                variant => format!("{label} ({variant})"),
+            };
+            let start = Instant::now();
+            let span = info_span!("solve", year, day = expected.day, part = %expected.part, variant);
+            let result = span.in_scope(|| (solution.solve)(&input, expected.part, variant, &Params::default()));
+            let elapsed = start.elapsed();
+            match result {
+                Ok(answer) if answer.to_string() == expected.answer => println!("{label}: ok ({elapsed:.1?})"),
+                Ok(answer) => {
+                    println!("{label}: wrong answer, expected\n{}\ngot\n{answer}", expected.answer);
+                    all_correct = false;
+                }
+                Err(err) => {
+                    println!("{label}: {err}");
+                    all_correct = false;
+                }
+            }
+        }
+    }
+    Ok(all_correct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore = "solves every puzzle on its real input, run with `cargo test --release -- --ignored`"]
+    fn real_inputs_give_recorded_answers() {
+        let inputs = Inputs::from_env();
+        for year in days::years() {
+            assert!(run(year, None, &inputs).unwrap(), "{year}");
+        }
+    }
+}
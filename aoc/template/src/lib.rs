@@ -0,0 +1,31 @@
+use aoc_core::{Answer, Error, Params, Result, Solution};
+use parser::parse_input;
+use types::Line;
+
+mod parser;
+mod types;
+
+pub struct DAY_TEMPLATE_STRUCT;
+
+impl Solution for DAY_TEMPLATE_STRUCT {
+    type Input<'a> = Vec<Line<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(_lines: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Err(Error::Unimplemented)
+    }
+
+    fn part2(_lines: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Err(Error::Unimplemented)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_core::examples!(DAY_TEMPLATE_STRUCT);
+}
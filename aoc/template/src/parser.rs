@@ -0,0 +1,22 @@
+use aoc_core::{Result, parse_all_verbose};
+use aoc_parse::lines_of;
+use nom::{character::complete::not_line_ending, error::{context, VerboseError}};
+
+use crate::types::Line;
+
+type IResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
+
+pub fn parse_input(input: &str) -> Result<Vec<Line<'_>>> {
+    let lines = parse_all_verbose(input, parse_lines)?;
+    Ok(lines)
+}
+
+fn parse_lines(input: &str) -> IResult<'_, Vec<Line<'_>>> {
+    let (input, lines) = lines_of(parse_line)(input)?;
+    Ok((input, lines))
+}
+
+fn parse_line(input: &str) -> IResult<'_, Line<'_>> {
+    let (input, text) = context("a line", not_line_ending)(input)?;
+    Ok((input, Line(text)))
+}
@@ -0,0 +1,3 @@
+#[allow(dead_code)] // until the parts read the input
+#[derive(Debug)]
+pub struct Line<'a>(pub &'a str);
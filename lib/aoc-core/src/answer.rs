@@ -0,0 +1,103 @@
+use std::fmt::Display;
+
+/// The result of solving one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    Str(String),
+    /// Letters drawn on a screen, `true` being a lit pixel. Rows are top to bottom.
+    Grid(Vec<Vec<bool>>),
+}
+
+impl Answer {
+    /// Builds a grid answer from ASCII art, `#` marks a lit pixel, anything else a dark one.
+    pub fn grid(art: &str) -> Self {
+        Answer::Grid(art.lines()
+            .map(|line| line.chars().map(|c| c == '#').collect())
+            .collect())
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Str(s) => f.write_str(s),
+            Answer::Grid(rows) => {
+                for (i, row) in rows.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    for &lit in row {
+                        f.write_str(if lit { "#" } else { "." })?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Int(i64::try_from(n).expect("answer does not fit in i64"))
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i8, i16, i32, i64, u8, u16, u32, u64, usize, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Str(s.to_string())
+    }
+}
+
+impl From<Vec<Vec<bool>>> for Answer {
+    fn from(rows: Vec<Vec<bool>>) -> Self {
+        Answer::Grid(rows)
+    }
+}
+
+/// Lets tests compare against plain values, e.g. `assert_eq!(24000, Day01::solve(INPUT, Part::One).unwrap())`.
+impl PartialEq<Answer> for i64 {
+    fn eq(&self, other: &Answer) -> bool {
+        matches!(other, Answer::Int(n) if n == self)
+    }
+}
+
+impl PartialEq<Answer> for &str {
+    fn eq(&self, other: &Answer) -> bool {
+        matches!(other, Answer::Str(s) if s == self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_works() {
+        assert_eq!("-12", Answer::from(-12).to_string());
+        assert_eq!("CQQBBJFCS", Answer::from("CQQBBJFCS").to_string());
+        assert_eq!("#..#\n.##.", Answer::grid("#..#\n.##.").to_string());
+    }
+
+    #[test]
+    fn compares_with_plain_values() {
+        assert_eq!(42, Answer::from(42u64));
+        assert_eq!("abc", Answer::from("abc".to_string()));
+        assert_ne!(42, Answer::from("42"));
+    }
+}
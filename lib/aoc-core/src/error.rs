@@ -0,0 +1,171 @@
+use std::fmt::Display;
+
+use nom::error::{VerboseError, VerboseErrorKind};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The puzzle input is malformed.
+    Parse(ParseError),
+    /// The input parsed fine, but the solver could not find an answer in it.
+    NoSolution(String),
+    /// The part has not been solved (yet).
+    Unimplemented,
+    /// A parameter is set to a value of the wrong type.
+    InvalidParam { name: String, value: String },
+    /// A parameter is set or read that the solution does not declare.
+    UnknownParam(String),
+    /// A variant is selected that the solution does not declare for the part.
+    UnknownVariant(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "invalid input: {err}"),
+            Error::NoSolution(reason) => write!(f, "no solution: {reason}"),
+            Error::Unimplemented => f.write_str("not implemented"),
+            Error::InvalidParam { name, value } => write!(f, "invalid parameter {name}={value:?}"),
+            Error::UnknownParam(name) => write!(f, "unknown parameter '{name}'"),
+            Error::UnknownVariant(name) => write!(f, "unknown variant '{name}'"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+/// Where and why parsing the puzzle input failed. Lines and columns start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    /// Reports an error at the start of `rest`, which has to be a suffix (or any other
+    /// subslice) of `input`.
+    pub fn at(input: &str, rest: &str, expected: impl Into<String>) -> Self {
+        let offset = (rest.as_ptr() as usize).saturating_sub(input.as_ptr() as usize);
+        assert!(offset <= input.len(), "rest is not a part of the input");
+
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+        let column = before[line_start..].chars().count() + 1;
+        let found = match rest.lines().next() {
+            None => "end of input".to_string(),
+            Some("") => "end of line".to_string(),
+            Some(text) => format!("{text:?}"),
+        };
+
+        ParseError {
+            line,
+            column,
+            expected: expected.into(),
+            found,
+        }
+    }
+
+    /// Converts the error of a nom parser that was run on `input`.
+    pub fn from_nom<'a, E: NomError<'a>>(input: &'a str, err: nom::Err<E>) -> Self {
+        match err {
+            nom::Err::Incomplete(_) => ParseError::at(input, &input[input.len()..], "more input"),
+            nom::Err::Error(err) | nom::Err::Failure(err) => err.into_parse_error(input),
+        }
+    }
+}
+
+/// The nom error types a `ParseError` can be made from.
+pub trait NomError<'a> {
+    fn into_parse_error(self, input: &'a str) -> ParseError;
+}
+
+/// Only knows which kind of parser failed, so a missing `tag("foo")` is reported as expecting
+/// "tag", not "foo".
+impl<'a> NomError<'a> for nom::error::Error<&'a str> {
+    fn into_parse_error(self, input: &'a str) -> ParseError {
+        ParseError::at(input, self.input, self.code.description().to_lowercase())
+    }
+}
+
+/// Reports the innermost `context` around the failed parser, like "a move" for
+/// `context("a move", tag("move "))`, or else the character that was expected.
+impl<'a> NomError<'a> for VerboseError<&'a str> {
+    fn into_parse_error(self, input: &'a str) -> ParseError {
+        let Some(&(rest, ref kind)) = self.errors.first() else {
+            return ParseError::at(input, input, "valid input");
+        };
+        let context = self.errors.iter().find_map(|(_, kind)| match kind {
+            VerboseErrorKind::Context(context) => Some(context.to_string()),
+            _ => None,
+        });
+        let expected = context.unwrap_or_else(|| match kind {
+            VerboseErrorKind::Char(c) => format!("{c:?}"),
+            VerboseErrorKind::Context(context) => context.to_string(),
+            VerboseErrorKind::Nom(kind) => kind.description().to_lowercase(),
+        });
+        ParseError::at(input, rest, expected)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: expected {}, found {}", self.line, self.column, self.expected, self.found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{self, char, line_ending},
+        error::context,
+        sequence::{preceded, separated_pair},
+    };
+
+    use super::*;
+
+    #[test]
+    fn at_reports_line_and_column() {
+        let input = "1\n22\n3x3";
+        let err = ParseError::at(input, &input[7..], "a digit");
+        assert_eq!((3, 3), (err.line, err.column));
+        assert_eq!("line 3, column 3: expected a digit, found \"3\"", err.to_string());
+    }
+
+    #[test]
+    fn from_nom_works() {
+        let input = "12\nx3";
+        let err = separated_pair(complete::u32, line_ending, complete::u32::<_, nom::error::Error<_>>)(input).unwrap_err();
+        let err = ParseError::from_nom(input, err);
+        assert_eq!((2, 1), (err.line, err.column));
+        assert_eq!("digit", err.expected);
+        assert_eq!("\"x3\"", err.found);
+
+        // a plain nom error can't tell which tag it wanted
+        let err = tag::<_, _, nom::error::Error<_>>("move")(input).unwrap_err();
+        assert_eq!("tag", ParseError::from_nom(input, err).expected);
+    }
+
+    #[test]
+    fn from_nom_verbose_reports_context_and_chars() {
+        let input = "move 1\nmove x";
+        let line = || context("a move", preceded(tag("move "), complete::u32::<_, VerboseError<_>>));
+        let err = separated_pair(line(), line_ending, line())(input).unwrap_err();
+        let err = ParseError::from_nom(input, err);
+        assert_eq!((2, 6), (err.line, err.column));
+        assert_eq!("a move", err.expected);
+
+        let err = separated_pair(complete::u32, char(','), complete::u32::<_, VerboseError<_>>)("1;2").unwrap_err();
+        assert_eq!("line 1, column 2: expected ',', found \";2\"", ParseError::from_nom("1;2", err).to_string());
+    }
+}
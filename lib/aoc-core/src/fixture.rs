@@ -0,0 +1,175 @@
+use std::{fs, path::Path};
+
+use crate::{normalize, Error, Params, Part, Solution, Variant};
+
+/// A puzzle example stored as a file: a header with the expected answers and parameters,
+/// a `---` line and then the example input.
+///
+/// ```text
+/// part1: 26
+/// row: 10
+/// ---
+/// Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+/// ```
+///
+/// A grid answer leaves the value empty and continues on lines indented by two spaces.
+/// Empty lines and `#` comments in the header are skipped. The file is normalized like a
+/// puzzle input first, so `\r\n` line breaks work too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    pub name: String,
+    /// Answers in the form `Answer` displays them.
+    pub expected: Vec<(Part, String)>,
+    pub params: Params,
+    pub input: String,
+}
+
+impl Fixture {
+    pub fn parse(name: &str, text: &str) -> Result<Fixture, String> {
+        let text = normalize(text);
+        let (header, input) = text.split_once("\n---\n")
+            .or_else(|| text.strip_suffix("\n---").map(|header| (header, "")))
+            .or_else(|| text.strip_prefix("---\n").map(|input| ("", input)))
+            .ok_or_else(|| format!("{name}: missing the `---` line after the header"))?;
+
+        let mut fields: Vec<(&str, String)> = Vec::new();
+        let mut open_grid = false;
+        for (idx, line) in header.lines().enumerate() {
+            let line_num = idx + 1;
+            if let Some(row) = line.strip_prefix("  ") {
+                match fields.last_mut() {
+                    Some((_, value)) if open_grid => {
+                        if !value.is_empty() {
+                            value.push('\n');
+                        }
+                        value.push_str(row);
+                    }
+                    _ => return Err(format!("{name} line {line_num}: grid row without a grid answer")),
+                }
+                continue;
+            }
+            open_grid = false;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line.split_once(':')
+                .ok_or_else(|| format!("{name} line {line_num}: expected `name: value`"))?;
+            let (key, value) = (key.trim(), value.trim().to_string());
+            if fields.iter().any(|(k, _)| *k == key) {
+                return Err(format!("{name} line {line_num}: {key} is set twice"));
+            }
+            open_grid = value.is_empty();
+            fields.push((key, value));
+        }
+
+        let mut fixture = Fixture {
+            name: name.to_string(),
+            expected: Vec::new(),
+            params: Params::default(),
+            input: input.to_string(),
+        };
+        for (key, value) in fields {
+            if value.is_empty() {
+                return Err(format!("{name}: {key} has an empty value"));
+            }
+            match key {
+                "part1" => fixture.expected.push((Part::One, value)),
+                "part2" => fixture.expected.push((Part::Two, value)),
+                _ => fixture.params.set(key, value),
+            }
+        }
+        Ok(fixture)
+    }
+
+    /// Loads every `*.txt` file in `dir`, sorted by name.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Vec<Fixture>, String> {
+        let dir = dir.as_ref();
+        let entries = fs::read_dir(dir).map_err(|err| format!("could not read {}: {err}", dir.display()))?;
+        let mut paths = entries
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| format!("could not read {}: {err}", dir.display()))?;
+        paths.retain(|path| path.extension().is_some_and(|ext| ext == "txt"));
+        paths.sort();
+
+        paths.iter()
+            .map(|path| {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                let text = fs::read_to_string(path).map_err(|err| format!("could not read {}: {err}", path.display()))?;
+                Fixture::parse(&name, &text)
+            })
+            .collect()
+    }
+}
+
+/// Solves every example in `dir`, with every variant of the parts, and panics with all
+/// mismatches. Parts that are not implemented yet are skipped. Used by `examples!`.
+pub fn check_examples<S: Solution>(dir: impl AsRef<Path>) {
+    let fixtures = Fixture::load_dir(&dir).unwrap_or_else(|err| panic!("{err}"));
+    assert!(!fixtures.is_empty(), "no examples in {}", dir.as_ref().display());
+
+    let mut failures = Vec::new();
+    for fixture in &fixtures {
+        if fixture.expected.is_empty() {
+            failures.push(format!("{}: no expected answers", fixture.name));
+        }
+        for (part, expected) in &fixture.expected {
+            let variants = S::VARIANTS.iter().filter(|v| v.part == *part).map(|v| v.name);
+            for variant in std::iter::once(Variant::DEFAULT).chain(variants) {
+                let mut label = format!("{} part{part}", fixture.name);
+                if variant != Variant::DEFAULT {
+                    label = format!("{label} ({variant})");
+                }
+                match S::solve_variant(&fixture.input, *part, variant, &fixture.params) {
+                    Ok(answer) if answer.to_string() == *expected => {}
+                    Ok(answer) => failures.push(format!("{label}: expected\n{expected}\ngot\n{answer}")),
+                    Err(Error::Unimplemented) => eprintln!("{label}: skipped, not implemented"),
+                    Err(err) => failures.push(format!("{label}: {err}")),
+                }
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+/// Generates a test that checks every example in the crate's `fixtures` directory.
+#[macro_export]
+macro_rules! examples {
+    ($solution:ty) => {
+        #[test]
+        fn examples_work() {
+            $crate::check_examples::<$solution>(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_works() {
+        let fixture = Fixture::parse("a.txt", "# comment\npart1: 26\nrow: 10\npart2:\n  #.\n  .#\n---\n1\n2\n").unwrap();
+        assert_eq!(vec![(Part::One, "26".to_string()), (Part::Two, "#.\n.#".to_string())], fixture.expected);
+        assert_eq!(Ok(10), fixture.params.get("row"));
+        assert_eq!("1\n2", fixture.input);
+        assert_eq!("x", Fixture::parse("b.txt", "---\nx").unwrap().input);
+    }
+
+    #[test]
+    fn parse_handles_crlf() {
+        let fixture = Fixture::parse("a.txt", "part1: 26\r\npart2:\r\n  #.\r\n  .#\r\n---\r\n1\r\n2\r\n").unwrap();
+        assert_eq!(vec![(Part::One, "26".to_string()), (Part::Two, "#.\n.#".to_string())], fixture.expected);
+        assert_eq!("1\n2", fixture.input);
+        assert_eq!(Err("a.txt: part2 has an empty value".to_string()), Fixture::parse("a.txt", "part2:\r\n---\r\n"));
+    }
+
+    #[test]
+    fn parse_reports_mistakes() {
+        assert_eq!(Err("a.txt: missing the `---` line after the header".to_string()), Fixture::parse("a.txt", "part1: 1\n1"));
+        assert_eq!(Err("a.txt line 2: part1 is set twice".to_string()), Fixture::parse("a.txt", "part1: 1\npart1: 2\n---\n"));
+        assert_eq!(Err("a.txt: part2 has an empty value".to_string()), Fixture::parse("a.txt", "part2:\n---\n"));
+        assert!(Fixture::parse("a.txt", "part1\n---\n").is_err());
+    }
+}
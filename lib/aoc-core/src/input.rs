@@ -0,0 +1,31 @@
+use std::{borrow::Cow, fs, io, path::Path};
+
+/// Brings puzzle input into the shape the parsers expect: no byte order mark, `\n` line
+/// endings and no trailing whitespace. Leading whitespace is kept, some inputs are aligned
+/// with it.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input).trim_end();
+    if input.contains("\r\n") {
+        Cow::Owned(input.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+/// Reads and normalizes a puzzle input file.
+pub fn read_input(path: impl AsRef<Path>) -> io::Result<String> {
+    let input = fs::read_to_string(path)?;
+    Ok(normalize(&input).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_works() {
+        assert_eq!("    [D]\n1 2\n\n3", normalize("\u{feff}    [D]\r\n1 2\r\n\r\n3\r\n\r\n"));
+        assert_eq!("1\n2", normalize("1\n2\n"));
+        assert!(matches!(normalize("1\n2"), Cow::Borrowed(_)));
+    }
+}
@@ -0,0 +1,169 @@
+mod answer;
+mod error;
+mod fixture;
+mod input;
+mod params;
+mod parse;
+mod variant;
+
+use std::{fmt::Display, str::FromStr};
+
+pub use answer::Answer;
+pub use error::{Error, NomError, ParseError, Result};
+pub use fixture::{check_examples, Fixture};
+pub use input::{normalize, read_input};
+pub use params::{Param, Params};
+pub use parse::{parse_all, parse_all_verbose};
+pub use variant::Variant;
+/// Diagnostics for the days, shown by the runner's `--log`.
+pub use tracing;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("invalid part '{s}', expected 1 or 2")),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Part::One => "1",
+            Part::Two => "2",
+        })
+    }
+}
+
+/// A day's puzzle: the input is parsed once and then shared by both parts.
+pub trait Solution {
+    /// Parsed puzzle input, free to borrow from the raw input text.
+    type Input<'a>;
+
+    /// Parameters the parts read from their `Params`, with the values for the real input.
+    const PARAMS: &'static [Param] = &[];
+
+    /// Alternative implementations of the parts, selectable by name.
+    const VARIANTS: &'static [Variant] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input<'_>>;
+    fn part1(input: &Self::Input<'_>, params: &Params) -> Result<Answer>;
+    fn part2(input: &Self::Input<'_>, params: &Params) -> Result<Answer>;
+
+    /// Solves one part of the puzzle with default parameters.
+    fn solve(input: &str, part: Part) -> Result<Answer> {
+        Self::solve_with(input, part, &Params::default())
+    }
+
+    /// Solves one part of the puzzle, the raw input is normalized first. Parameters that are
+    /// not set get their declared defaults.
+    fn solve_with(input: &str, part: Part, params: &Params) -> Result<Answer> {
+        let params = &params.with_defaults(Self::PARAMS)?;
+        let input = normalize(input);
+        let input = Self::parse(&input)?;
+        match part {
+            Part::One => Self::part1(&input, params),
+            Part::Two => Self::part2(&input, params),
+        }
+    }
+
+    /// Solves one part with the named variant, `Variant::DEFAULT` being `part1` or `part2`.
+    fn solve_variant(input: &str, part: Part, variant: &str, params: &Params) -> Result<Answer> {
+        if variant == Variant::DEFAULT {
+            return Self::solve_with(input, part, params);
+        }
+        let variant = Self::VARIANTS.iter()
+            .find(|v| v.part == part && v.name == variant)
+            .ok_or_else(|| Error::UnknownVariant(variant.to_string()))?;
+        let params = &params.with_defaults(Self::PARAMS)?;
+        (variant.solve)(&normalize(input), params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input<'a> = Vec<&'a str>;
+
+        const PARAMS: &'static [Param] = &[
+            Param { name: "sep", default: "", about: "put between the lines in part 2" },
+        ];
+
+        const VARIANTS: &'static [Variant] = &[
+            Variant { name: "split", part: Part::One, solve: |input, _| Ok(input.split('\n').count().into()) },
+        ];
+
+        fn parse(input: &str) -> Result<Self::Input<'_>> {
+            if input.is_empty() {
+                return Err(ParseError::at(input, input, "a line").into());
+            }
+            Ok(input.lines().collect())
+        }
+
+        fn part1(input: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+            Ok(input.len().into())
+        }
+
+        fn part2(input: &Self::Input<'_>, params: &Params) -> Result<Answer> {
+            Ok(input.join(&params.get::<String>("sep")?).into())
+        }
+    }
+
+    #[test]
+    fn part_from_str_works() {
+        assert_eq!(Ok(Part::One), "1".parse());
+        assert_eq!(Ok(Part::Two), "2".parse());
+        assert!("3".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn solve_dispatches_parts() {
+        assert_eq!(3, Sum::solve("a\nb\nc", Part::One).unwrap());
+        assert_eq!("abc", Sum::solve("a\nb\nc", Part::Two).unwrap());
+        assert_eq!("abc", Sum::solve("a\r\nb\r\nc\r\n", Part::Two).unwrap());
+    }
+
+    #[test]
+    fn solve_with_fills_in_params() {
+        let params: Params = [("sep", "-")].into_iter().collect();
+        assert_eq!("a-b", Sum::solve_with("a\nb", Part::Two, &params).unwrap());
+
+        let params: Params = [("separator", "-")].into_iter().collect();
+        assert_eq!(Err(Error::UnknownParam("separator".to_string())), Sum::solve_with("a\nb", Part::Two, &params));
+    }
+
+    #[test]
+    fn solve_variant_works() {
+        let params = Params::default();
+        assert_eq!(3, Sum::solve_variant("a\nb\nc\n", Part::One, "split", &params).unwrap());
+        assert_eq!(3, Sum::solve_variant("a\nb\nc", Part::One, Variant::DEFAULT, &params).unwrap());
+        assert_eq!(
+            Err(Error::UnknownVariant("split".to_string())),
+            Sum::solve_variant("a", Part::Two, "split", &params)
+        );
+    }
+
+    #[test]
+    fn solve_reports_parse_errors() {
+        let err = Sum::solve("", Part::One).unwrap_err();
+        assert_eq!("invalid input: line 1, column 1: expected a line, found end of input", err.to_string());
+    }
+}
@@ -0,0 +1,83 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use crate::{Error, Result};
+
+/// A parameter a solution reads, declared in `Solution::PARAMS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    /// The value for the real puzzle input.
+    pub default: &'static str,
+    pub about: &'static str,
+}
+
+/// Named values a puzzle depends on besides its input, e.g. the row day 15 looks at, which
+/// is different for the examples than for the real input.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    /// All declared parameters set to their defaults.
+    pub fn defaults(declared: &[Param]) -> Self {
+        declared.iter().map(|param| (param.name, param.default)).collect()
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.0.insert(name.into(), value.into());
+    }
+
+    /// Fills in the defaults of the `declared` parameters that are not set. Setting a
+    /// parameter that is not declared is an error.
+    pub fn with_defaults(&self, declared: &[Param]) -> Result<Params> {
+        if let Some(name) = self.0.keys().find(|name| !declared.iter().any(|param| param.name == *name)) {
+            return Err(Error::UnknownParam(name.clone()));
+        }
+        let mut params = Params::defaults(declared);
+        params.0.extend(self.0.clone());
+        Ok(params)
+    }
+
+    /// Looks up and parses `name`.
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<T> {
+        let value = self.0.get(name).ok_or_else(|| Error::UnknownParam(name.to_string()))?;
+        value.parse().map_err(|_| Error::InvalidParam {
+            name: name.to_string(),
+            value: value.clone(),
+        })
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Params {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Params(iter.into_iter().map(|(name, value)| (name.into(), value.into())).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECLARED: &[Param] = &[
+        Param { name: "row", default: "2000000", about: "row to look at" },
+        Param { name: "bound", default: "4000000", about: "largest coordinate" },
+    ];
+
+    #[test]
+    fn get_works() {
+        let params: Params = [("row", "10"), ("name", "x")].into_iter().collect();
+        assert_eq!(Ok(10), params.get("row"));
+        assert_eq!("invalid parameter name=\"x\"", params.get::<i64>("name").unwrap_err().to_string());
+        assert_eq!(Err(Error::UnknownParam("bound".to_string())), params.get::<i64>("bound"));
+    }
+
+    #[test]
+    fn with_defaults_works() {
+        let params: Params = [("row", "10")].into_iter().collect();
+        let params = params.with_defaults(DECLARED).unwrap();
+        assert_eq!(Ok(10), params.get("row"));
+        assert_eq!(Ok(4_000_000), params.get("bound"));
+
+        let params: Params = [("rows", "10")].into_iter().collect();
+        assert_eq!(Err(Error::UnknownParam("rows".to_string())), params.with_defaults(DECLARED));
+    }
+}
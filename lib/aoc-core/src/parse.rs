@@ -0,0 +1,49 @@
+use nom::{error::{Error, VerboseError}, Parser};
+
+use crate::{NomError, ParseError};
+
+/// Runs a nom parser over the whole input. Unlike calling the parser directly, leftovers
+/// are an error, reported at the first unparsed character. Trailing whitespace is fine.
+pub fn parse_all<'a, O, P>(input: &'a str, parser: P) -> Result<O, ParseError>
+where
+    P: Parser<&'a str, O, Error<&'a str>>,
+{
+    run(input, parser)
+}
+
+/// Like `parse_all`, for parsers built on `VerboseError`, so that errors can say what was
+/// expected with `context`.
+pub fn parse_all_verbose<'a, O, P>(input: &'a str, parser: P) -> Result<O, ParseError>
+where
+    P: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    run(input, parser)
+}
+
+fn run<'a, O, E: NomError<'a>>(input: &'a str, mut parser: impl Parser<&'a str, O, E>) -> Result<O, ParseError> {
+    let (rest, output) = parser.parse(input).map_err(|err| ParseError::from_nom(input, err))?;
+    let rest = rest.trim_start();
+    if !rest.is_empty() {
+        return Err(ParseError::at(input, rest, "end of input"));
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{character::complete::{self, line_ending}, multi::separated_list1};
+
+    use super::*;
+
+    #[test]
+    fn parse_all_works() {
+        let numbers = parse_all("1\n2\n3\n", separated_list1(line_ending, complete::u32));
+        assert_eq!(Ok(vec![1, 2, 3]), numbers);
+    }
+
+    #[test]
+    fn parse_all_reports_leftovers() {
+        let err = parse_all("1\n2\nx\n4", separated_list1(line_ending, complete::u32)).unwrap_err();
+        assert_eq!("line 3, column 1: expected end of input, found \"x\"", err.to_string());
+    }
+}
@@ -0,0 +1,15 @@
+use crate::{Answer, Params, Part, Result};
+
+/// An alternative implementation of one part, declared in `Solution::VARIANTS`. It starts
+/// from the normalized raw input, so it can bring its own parser.
+#[derive(Debug, Clone, Copy)]
+pub struct Variant {
+    pub name: &'static str,
+    pub part: Part,
+    pub solve: fn(&str, &Params) -> Result<Answer>,
+}
+
+impl Variant {
+    /// Name of the implementation in `Solution::part1` and `Solution::part2`.
+    pub const DEFAULT: &'static str = "default";
+}
@@ -0,0 +1,214 @@
+//! nom combinators for the shapes puzzle inputs keep coming in. Line breaks may be `\n` or
+//! `\r\n` everywhere, and like nom's own `separated_list1`, none of them eat a trailing line break.
+//! They work with any nom error type, so they fit in with `VerboseError` and `context` too.
+
+use std::str::FromStr;
+
+use grid::{Grid, GridError};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1, line_ending, one_of, space0},
+    combinator::{map_res, opt, recognize, value},
+    error::{ErrorKind, FromExternalError, ParseError},
+    multi::{many1_count, separated_list1},
+    sequence::{pair, preceded},
+    IResult, Parser,
+};
+
+/// One or more empty lines, along with the line break before them.
+pub fn blank_lines<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, (), E> {
+    value((), pair(line_ending, many1_count(line_ending)))(input)
+}
+
+/// Groups of lines separated by blank lines, each parsed by `block`. A group that does not
+/// parse is an error, not the end of the list.
+pub fn blocks<'a, O, E, F>(mut block: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    E: ParseError<&'a str>,
+    F: Parser<&'a str, O, E>,
+{
+    move |input| {
+        let (mut input, first) = block.parse(input)?;
+        let mut blocks = vec![first];
+        loop {
+            match blank_lines::<E>(input) {
+                Ok((next, ())) if !next.trim().is_empty() => {
+                    let (next, item) = block.parse(next)?;
+                    blocks.push(item);
+                    input = next;
+                }
+                _ => return Ok((input, blocks)),
+            }
+        }
+    }
+}
+
+/// One `line` per line, up to an empty line or the end of the input. A line that does not
+/// parse is an error, not the end of the list, so errors point at the line that is wrong.
+pub fn lines_of<'a, O, E, F>(mut line: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    E: ParseError<&'a str>,
+    F: Parser<&'a str, O, E>,
+{
+    move |input| {
+        let (mut input, first) = line.parse(input)?;
+        let mut lines = vec![first];
+        loop {
+            match line_ending::<_, E>(input) {
+                Ok((next, _)) if !next.starts_with(['\r', '\n']) && !next.trim().is_empty() => {
+                    let (next, item) = line.parse(next)?;
+                    lines.push(item);
+                    input = next;
+                }
+                _ => return Ok((input, lines)),
+            }
+        }
+    }
+}
+
+/// A whole number with an optional sign, of any type that can be parsed from a string.
+pub fn number<'a, T, E>(input: &'a str) -> IResult<&'a str, T, E>
+where
+    T: FromStr,
+    E: ParseError<&'a str> + FromExternalError<&'a str, T::Err>,
+{
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Numbers separated by `separator` and any spaces after it, like `1, -2, 3` or `4 5  6`.
+pub fn number_list<'a, T, E>(separator: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>, E>
+where
+    T: FromStr,
+    E: ParseError<&'a str> + FromExternalError<&'a str, T::Err>,
+{
+    separated_list1(pair(tag(separator), space0), number)
+}
+
+/// `key=value`, returning the value, as in `x=3, y=-2`.
+pub fn key_value<'a, O, E, F>(key: &'a str, value: F) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
+where
+    E: ParseError<&'a str>,
+    F: Parser<&'a str, O, E>,
+{
+    preceded(pair(tag(key), char('=')), value)
+}
+
+/// Three `item`s separated by `separator`, as in the `1,2,3` of a point.
+pub fn separated_triple<'a, O, O2, E, F, G>(mut item: F, mut separator: G) -> impl FnMut(&'a str) -> IResult<&'a str, (O, O, O), E>
+where
+    E: ParseError<&'a str>,
+    F: Parser<&'a str, O, E>,
+    G: Parser<&'a str, O2, E>,
+{
+    move |input| {
+        let (input, a) = item.parse(input)?;
+        let (input, _) = separator.parse(input)?;
+        let (input, b) = item.parse(input)?;
+        let (input, _) = separator.parse(input)?;
+        let (input, c) = item.parse(input)?;
+        Ok((input, (a, b, c)))
+    }
+}
+
+/// A grid of one cell per character, up to an empty line or the end of the input, checked
+/// like `Grid::parse` does.
+pub fn grid_of<'a, T, E>(mut cell: impl FnMut(char) -> Option<T>) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>, E>
+where
+    E: ParseError<&'a str>,
+{
+    move |input| {
+        // the grid goes on until an empty line or the end of the input
+        let mut start = 0;
+        let mut end = 0;
+        for line in input.split_inclusive('\n') {
+            let content = line.trim_end_matches(['\r', '\n']);
+            if content.is_empty() {
+                break;
+            }
+            end = start + content.len();
+            start += line.len();
+        }
+        let (block, rest) = input.split_at(end);
+        let grid = Grid::parse_lines(block, &mut cell).map_err(|(at, err)| {
+            let kind = match err {
+                GridError::Cell => ErrorKind::Char,
+                GridError::RowWidth(_) => ErrorKind::Verify,
+                GridError::Empty => ErrorKind::Many1,
+            };
+            nom::Err::Error(E::from_error_kind(at, kind))
+        })?;
+        Ok((rest, grid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        character::complete,
+        error::{context, Error, VerboseError, VerboseErrorKind},
+    };
+
+    use super::*;
+
+    #[test]
+    fn blocks_work() {
+        let result: IResult<_, _> = blocks(lines_of(complete::u32))("1\n2\n\n3");
+        assert_eq!(Ok(("", vec![vec![1, 2], vec![3]])), result);
+
+        let result: IResult<_, _> = blocks(lines_of(complete::u32))("1\r\n2\r\n\r\n\r\n3\r\n4\r\n");
+        assert_eq!(Ok(("\r\n", vec![vec![1, 2], vec![3, 4]])), result);
+    }
+
+    #[test]
+    fn lines_that_do_not_parse_are_errors() {
+        let result: IResult<_, _> = lines_of(complete::u32)("1\nx\n3");
+        assert_eq!(Err(nom::Err::Error(Error::new("x\n3", ErrorKind::Digit))), result);
+        let result: IResult<_, _> = blocks(lines_of(complete::u32))("1\n\nx");
+        assert_eq!(Err(nom::Err::Error(Error::new("x", ErrorKind::Digit))), result);
+
+        let result: IResult<_, _> = lines_of(complete::u32)("1\n2\n\nx");
+        assert_eq!(Ok(("\n\nx", vec![1, 2])), result);
+    }
+
+    #[test]
+    fn numbers_work() {
+        assert_eq!(Ok(("", -12i64)), number::<_, Error<_>>("-12"));
+        assert_eq!(Ok(("", 7u8)), number::<_, Error<_>>("+7"));
+        assert!(number::<u8, Error<_>>("300").is_err());
+        assert_eq!(Ok(("\n", vec![1, -2, 3])), number_list::<i32, Error<_>>(",")("1, -2,3\n"));
+        assert_eq!(Ok(("", vec![4, 5, 6])), number_list::<u32, Error<_>>(" ")("4 5  6"));
+    }
+
+    #[test]
+    fn key_value_and_triple_work() {
+        let mut xy = nom::sequence::separated_pair(key_value("x", number::<i64, Error<_>>), tag(", "), key_value("y", number));
+        assert_eq!(Ok(("", (2, -18))), xy("x=2, y=-18"));
+        let mut triple = separated_triple(number::<i32, Error<_>>, char(','));
+        assert_eq!(Ok(("\n", (1, 2, -3))), triple("1,2,-3\n"));
+        assert!(triple("1,2").is_err());
+    }
+
+    #[test]
+    fn grid_of_works() {
+        let digits = |c: char| c.to_digit(10);
+        let (rest, grid) = grid_of::<_, Error<_>>(digits)("12\r\n34\r\n\r\n5").unwrap();
+        assert_eq!("\r\n\r\n5", rest);
+        assert_eq!("12\n34", grid.to_string());
+        assert_eq!("\n", grid_of::<_, Error<_>>(digits)("12\n34\n").unwrap().0);
+
+        let Err(nom::Err::Error(err)) = grid_of::<_, Error<_>>(digits)("12\n3x") else { panic!("x is no digit") };
+        assert_eq!(("x", ErrorKind::Char), (err.input, err.code));
+        let Err(nom::Err::Error(err)) = grid_of::<_, Error<_>>(digits)("12\n345") else { panic!("rows differ") };
+        assert_eq!(("5", ErrorKind::Verify), (err.input, err.code));
+        let Err(nom::Err::Error(err)) = grid_of::<_, Error<_>>(digits)("\n12") else { panic!("no rows") };
+        assert_eq!(("", ErrorKind::Many1), (err.input, err.code));
+    }
+
+    #[test]
+    fn combinators_work_with_verbose_errors() {
+        let Err(nom::Err::Error(err)) = context("numbers", lines_of(number::<u32, VerboseError<_>>))("1\nx") else {
+            panic!("x is no number")
+        };
+        assert_eq!(vec![("x", VerboseErrorKind::Nom(ErrorKind::Digit)), ("1\nx", VerboseErrorKind::Context("numbers"))], err.errors);
+    }
+}
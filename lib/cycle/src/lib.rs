@@ -0,0 +1,166 @@
+//! Finding where a long simulation starts repeating itself, so its outcome after many steps
+//! can be worked out from the first cycle.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// After `start` steps, the states repeat every `len` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Cycle {
+    /// The earliest step that is in the same state as step `n`.
+    pub fn equivalent(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+}
+
+/// Steps `state` until a state comes around again, telling them apart by `key`.
+///
+/// Runs forever if the states never repeat.
+pub fn find<S, K: Hash + Eq>(mut state: S, mut step: impl FnMut(&mut S), mut key: impl FnMut(&S) -> K) -> Cycle {
+    let mut seen = HashMap::new();
+    for n in 0.. {
+        if let Some(start) = seen.insert(key(&state), n) {
+            return Cycle { start, len: n - start };
+        }
+        step(&mut state);
+    }
+    unreachable!("ran out of steps")
+}
+
+/// The value after `steps` steps of a simulation where, once the states repeat, `value` grows by
+/// the same amount every cycle. States are told apart by `key`, which needs to capture whatever
+/// decides how the simulation carries on.
+pub fn extrapolate<S, K: Hash + Eq>(
+    mut state: S,
+    steps: usize,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+    mut value: impl FnMut(&S) -> i64,
+) -> i64 {
+    let mut seen = HashMap::new();
+    let mut values = Vec::new();
+    for n in 0..=steps {
+        values.push(value(&state));
+        if let Some(start) = seen.insert(key(&state), n) {
+            let cycle = Cycle { start, len: n - start };
+            let gain = values[n] - values[start];
+            let cycles = ((steps - start) / cycle.len) as i64;
+            return values[cycle.equivalent(steps)] + cycles * gain;
+        }
+        if n < steps {
+            step(&mut state);
+        }
+    }
+    values[steps]
+}
+
+/// Floyd's tortoise and hare on the sequence `x0, f(x0), f(f(x0)), ...`, keeping only two
+/// values around instead of every state seen.
+pub fn floyd<T: PartialEq + Clone>(x0: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    let mut tortoise = f(&x0);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let next = f(&hare);
+        hare = f(&next);
+    }
+
+    let mut start = 0;
+    tortoise = x0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut len = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        len += 1;
+    }
+    Cycle { start, len }
+}
+
+/// Brent's algorithm, like `floyd` but with fewer calls to `f`.
+pub fn brent<T: PartialEq + Clone>(x0: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = f(&hare);
+        len += 1;
+    }
+
+    let mut tortoise = x0.clone();
+    let mut hare = x0;
+    for _ in 0..len {
+        hare = f(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+    Cycle { start, len }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, 3, 4, 5, 3, 4, 5, ...
+    fn next(&x: &u32) -> u32 {
+        if x == 5 { 3 } else { x + 1 }
+    }
+
+    #[test]
+    fn detectors_agree() {
+        let cycle = Cycle { start: 3, len: 3 };
+        assert_eq!(cycle, floyd(0, next));
+        assert_eq!(cycle, brent(0, next));
+        assert_eq!(cycle, find(0, |x| *x = next(x), |&x| x));
+        assert_eq!(Cycle { start: 0, len: 3 }, brent(4, next));
+    }
+
+    #[test]
+    fn equivalent_works() {
+        let cycle = Cycle { start: 3, len: 3 };
+        assert_eq!(2, cycle.equivalent(2));
+        assert_eq!(4, cycle.equivalent(4));
+        assert_eq!(3, cycle.equivalent(9));
+        assert_eq!(5, cycle.equivalent(1_000_000_001));
+    }
+
+    #[test]
+    fn extrapolate_works() {
+        // a counter that goes round 0..4 and a total that adds the counter every step
+        let step = |(counter, total): &mut (i64, i64)| {
+            *counter = (*counter + 1) % 4;
+            *total += *counter;
+        };
+        let simulate = |steps| (0..steps).fold((0, 0), |mut state, _| {
+            step(&mut state);
+            state
+        }).1;
+        for steps in [0, 3, 4, 10, 1001] {
+            assert_eq!(simulate(steps), extrapolate((0, 0), steps, step, |s| s.0, |s| s.1));
+        }
+        assert_eq!(1_500_000_000_000, extrapolate((0, 0), 1_000_000_000_000, step, |s| s.0, |s| s.1));
+    }
+}
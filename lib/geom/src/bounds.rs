@@ -0,0 +1,111 @@
+use crate::{Coord, Point2, Point3};
+
+/// Points that can be compared coordinate by coordinate.
+pub trait Corner: Copy {
+    /// The smallest of each coordinate.
+    fn min_each(self, other: Self) -> Self;
+    /// The largest of each coordinate.
+    fn max_each(self, other: Self) -> Self;
+    /// Whether every coordinate is at most the one of `other`.
+    fn all_le(self, other: Self) -> bool;
+}
+
+impl<T: Coord> Corner for Point2<T> {
+    fn min_each(self, other: Self) -> Self {
+        Point2::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    fn max_each(self, other: Self) -> Self {
+        Point2::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    fn all_le(self, other: Self) -> bool {
+        self.x <= other.x && self.y <= other.y
+    }
+}
+
+impl<T: Coord> Corner for Point3<T> {
+    fn min_each(self, other: Self) -> Self {
+        Point3::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+    }
+
+    fn max_each(self, other: Self) -> Self {
+        Point3::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+    }
+
+    fn all_le(self, other: Self) -> bool {
+        self.x <= other.x && self.y <= other.y && self.z <= other.z
+    }
+}
+
+/// The smallest box around some points, `min` and `max` included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds<P> {
+    pub min: P,
+    pub max: P,
+}
+
+impl<P: Corner> Bounds<P> {
+    /// `None` when there are no points.
+    pub fn of(points: impl IntoIterator<Item = P>) -> Option<Self> {
+        points.into_iter().fold(None, |bounds, p| match bounds {
+            None => Some(Bounds { min: p, max: p }),
+            Some(Bounds { min, max }) => Some(Bounds { min: min.min_each(p), max: max.max_each(p) }),
+        })
+    }
+
+    pub fn contains(&self, p: P) -> bool {
+        self.min.all_le(p) && p.all_le(self.max)
+    }
+}
+
+impl<T: Coord> Bounds<Point2<T>> {
+    /// Grows the box by `by` on every side.
+    pub fn grow(self, by: T) -> Self {
+        let by = Point2::new(by, by);
+        Bounds { min: self.min - by, max: self.max + by }
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+}
+
+impl<T: Coord> Bounds<Point3<T>> {
+    /// Grows the box by `by` on every side.
+    pub fn grow(self, by: T) -> Self {
+        let by = Point3::new(by, by, by);
+        Bounds { min: self.min - by, max: self.max + by }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn of_works() {
+        let bounds = Bounds::of([Point2::new(2, 5), Point2::new(-1, 3), Point2::new(0, 7)]).unwrap();
+        assert_eq!(Bounds { min: Point2::new(-1, 3), max: Point2::new(2, 7) }, bounds);
+        assert_eq!((4, 5, 20), (bounds.width(), bounds.height(), bounds.area()));
+        assert!(bounds.contains(Point2::new(0, 3)));
+        assert!(!bounds.contains(Point2::new(0, 8)));
+        assert_eq!(None, Bounds::<Point3<i32>>::of([]));
+    }
+
+    #[test]
+    fn grow_works() {
+        let bounds = Bounds::of([Point3::new(1, 2, 3)]).unwrap().grow(1);
+        assert_eq!(Point3::new(0, 1, 2), bounds.min);
+        assert!(bounds.contains(Point3::new(2, 3, 4)));
+        assert!(!bounds.contains(Point3::new(2, 3, 5)));
+    }
+}
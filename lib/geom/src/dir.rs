@@ -0,0 +1,115 @@
+use crate::{Coord, Point2};
+
+/// A step along one of the axes, with `Up` towards smaller `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// Clockwise, starting upwards.
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    pub fn turn_right(self) -> Self {
+        Dir4::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Dir4::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Dir4::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn offset<T: Coord>(self) -> Point2<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match self {
+            Dir4::Up => Point2::new(zero, -one),
+            Dir4::Right => Point2::new(one, zero),
+            Dir4::Down => Point2::new(zero, one),
+            Dir4::Left => Point2::new(-one, zero),
+        }
+    }
+}
+
+/// A step to one of the 8 surrounding points, with `N` towards smaller `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    /// Clockwise, starting north.
+    pub const ALL: [Dir8; 8] = [Dir8::N, Dir8::NE, Dir8::E, Dir8::SE, Dir8::S, Dir8::SW, Dir8::W, Dir8::NW];
+
+    /// Turns clockwise by 45 degrees.
+    pub fn turn_right(self) -> Self {
+        Dir8::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns counterclockwise by 45 degrees.
+    pub fn turn_left(self) -> Self {
+        Dir8::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Dir8::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn offset<T: Coord>(self) -> Point2<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        let (x, y) = match self {
+            Dir8::N => (zero, -one),
+            Dir8::NE => (one, -one),
+            Dir8::E => (one, zero),
+            Dir8::SE => (one, one),
+            Dir8::S => (zero, one),
+            Dir8::SW => (-one, one),
+            Dir8::W => (-one, zero),
+            Dir8::NW => (-one, -one),
+        };
+        Point2::new(x, y)
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Dir8::ALL[dir as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning_works() {
+        assert_eq!(Dir4::Right, Dir4::Up.turn_right());
+        assert_eq!(Dir4::Left, Dir4::Up.turn_left());
+        assert_eq!(Dir4::Down, Dir4::Up.reverse());
+        assert_eq!(Dir8::NW, Dir8::N.turn_left());
+        assert_eq!(Dir8::SW, Dir8::NE.reverse());
+        assert_eq!(Dir8::W, Dir4::Left.into());
+    }
+
+    #[test]
+    fn offsets_work() {
+        assert_eq!(Point2::new(0, -1), Dir4::Up.offset::<i32>());
+        assert_eq!(Point2::new(-1, 1), Dir8::SW.offset::<i64>());
+        for dir in Dir4::ALL {
+            assert_eq!(Point2::<i32>::ORIGIN, dir.offset() + dir.reverse().offset());
+            assert_eq!(dir.offset::<i32>(), Dir8::from(dir).offset());
+        }
+    }
+}
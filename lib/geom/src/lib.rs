@@ -0,0 +1,7 @@
+mod bounds;
+mod dir;
+mod point;
+
+pub use bounds::{Bounds, Corner};
+pub use dir::{Dir4, Dir8};
+pub use point::{Coord, Point2, Point3};
@@ -0,0 +1,206 @@
+use std::{
+    fmt::Debug,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// A signed integer that points are made of.
+pub trait Coord:
+    Copy + Ord + Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {$(
+        impl Coord for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+        }
+    )*};
+}
+
+impl_coord!(i8, i16, i32, i64, i128, isize);
+
+/// A point or vector in the plane. `y` grows downwards, like the lines of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coord> Point2<T> {
+    pub const ORIGIN: Self = Point2 { x: T::ZERO, y: T::ZERO };
+
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// Every coordinate replaced by its sign, a step of at most one towards where `self` points.
+    pub fn signum(self) -> Self {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        crate::Dir4::ALL.into_iter().map(move |dir| self + dir.offset())
+    }
+
+    pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+        crate::Dir8::ALL.into_iter().map(move |dir| self + dir.offset())
+    }
+}
+
+impl<T: Coord> Point3<T> {
+    pub const ORIGIN: Self = Point3 { x: T::ZERO, y: T::ZERO, z: T::ZERO };
+
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        (self.x - other.x).abs()
+            .max((self.y - other.y).abs())
+            .max((self.z - other.z).abs())
+    }
+
+    pub fn signum(self) -> Self {
+        Point3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    /// The 6 points sharing a face with `self`.
+    pub fn neighbors6(self) -> impl Iterator<Item = Self> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        [
+            Point3::new(-one, zero, zero),
+            Point3::new(one, zero, zero),
+            Point3::new(zero, -one, zero),
+            Point3::new(zero, one, zero),
+            Point3::new(zero, zero, -one),
+            Point3::new(zero, zero, one),
+        ].into_iter().map(move |offset| self + offset)
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+macro_rules! impl_ops {
+    ($point:ident { $($c:ident),* }) => {
+        impl<T: Coord> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                $point { $($c: self.$c + rhs.$c),* }
+            }
+        }
+
+        impl<T: Coord> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                $point { $($c: self.$c - rhs.$c),* }
+            }
+        }
+
+        impl<T: Coord> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $point { $($c: -self.$c),* }
+            }
+        }
+
+        impl<T: Coord> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                $point { $($c: self.$c * rhs),* }
+            }
+        }
+
+        impl<T: Coord> AddAssign for $point<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Coord> SubAssign for $point<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+impl_ops!(Point2 { x, y });
+impl_ops!(Point3 { x, y, z });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_works() {
+        let mut p = Point2::new(1, 2) + Point2::new(3, -4);
+        assert_eq!(Point2::new(4, -2), p);
+        p -= Point2::new(1, 1);
+        assert_eq!(Point2::new(-6, 6), -p * 2);
+        assert_eq!(Point3::new(0, 1, -1), Point3::new(5, 3, -2).signum() - Point3::new(1, 0, 0));
+    }
+
+    #[test]
+    fn distances_work() {
+        let (a, b) = (Point2::new(1i64, 1), Point2::new(4, -1));
+        assert_eq!(5, a.manhattan(b));
+        assert_eq!(3, a.chebyshev(b));
+        assert_eq!(6, Point3::new(1, 2, 3).manhattan(Point3::ORIGIN));
+        assert_eq!(3, Point3::new(1, 2, 3).chebyshev(Point3::ORIGIN));
+    }
+
+    #[test]
+    fn neighbors_work() {
+        let p = Point2::new(0, 0);
+        assert_eq!(4, p.neighbors4().filter(|n| n.manhattan(p) == 1).count());
+        assert_eq!(8, p.neighbors8().filter(|n| n.chebyshev(p) == 1).count());
+        assert_eq!(6, Point3::new(1, 1, 1).neighbors6().filter(|n| n.manhattan(Point3::new(1, 1, 1)) == 1).count());
+    }
+}
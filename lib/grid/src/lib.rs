@@ -0,0 +1,289 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use aoc_core::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pos {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Pos {
+    pub fn new(x: usize, y: usize) -> Self {
+        Pos { x, y }
+    }
+}
+
+const NEIGHBORS4: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+const NEIGHBORS8: [(isize, isize); 8] = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+
+/// What was expected where parsing a grid failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridError {
+    /// A character `cell` turned down, or an empty first row.
+    Cell,
+    /// A row of a different width than the first.
+    RowWidth(usize),
+    /// No rows at all.
+    Empty,
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Cell => f.write_str("a grid cell"),
+            GridError::RowWidth(width) => write!(f, "a row of {width} cells"),
+            GridError::Empty => f.write_str("a grid"),
+        }
+    }
+}
+
+/// A rectangular grid, stored row by row from the top left.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Panics unless there are `width` cells for every row.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(width > 0 && cells.len().is_multiple_of(width), "{} cells do not fill rows of {width}", cells.len());
+        Grid { width, height: cells.len() / width, cells }
+    }
+
+    /// Parses one cell per character, one row per line. `cell` returns `None` for characters
+    /// that do not belong in the grid, and all rows need to be as wide as the first.
+    pub fn parse(input: &str, cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        Grid::parse_lines(input, cell).map_err(|(rest, err)| ParseError::at(input, rest, err.to_string()))
+    }
+
+    /// Like `parse`, but returns what went wrong along with the part of `input` where it did,
+    /// for parsers that report errors their own way.
+    pub fn parse_lines(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, (&str, GridError)> {
+        let mut width = None;
+        let mut cells = Vec::new();
+        let mut offset = 0;
+        for raw_line in input.split_inclusive('\n') {
+            let start = offset;
+            offset += raw_line.len();
+            let line = raw_line.strip_suffix('\n').unwrap_or(raw_line);
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            let mut count = 0;
+            for (i, c) in line.char_indices() {
+                let value = cell(c).ok_or((&input[start + i..], GridError::Cell))?;
+                cells.push(value);
+                count += 1;
+            }
+            match width {
+                None if count == 0 => return Err((&input[start..], GridError::Cell)),
+                None => width = Some(count),
+                Some(width) if width != count => {
+                    // either the first cell too many or the end of a short row
+                    let end = line.char_indices().nth(width).map_or(line.len(), |(i, _)| i);
+                    return Err((&input[start + end..], GridError::RowWidth(width)));
+                }
+                Some(_) => {}
+            }
+        }
+        let width = width.ok_or((input, GridError::Empty))?;
+        Ok(Grid::from_vec(width, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[pos.y * self.width + pos.x])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.y * self.width + pos.x])
+        } else {
+            None
+        }
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Pos::new(i % width, i / width))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Positions of the cells matching `pred`, row by row.
+    pub fn find<'a>(&'a self, mut pred: impl FnMut(&T) -> bool + 'a) -> impl Iterator<Item = Pos> + 'a {
+        self.iter().filter_map(move |(pos, value)| pred(value).then_some(pos))
+    }
+
+    /// `pos` moved by `dx` and `dy`, if that is still on the grid.
+    pub fn offset(&self, pos: Pos, dx: isize, dy: isize) -> Option<Pos> {
+        let x = pos.x.checked_add_signed(dx)?;
+        let y = pos.y.checked_add_signed(dy)?;
+        let pos = Pos::new(x, y);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// `pos` moved by `dx` and `dy`, coming back in on the other side when leaving the grid.
+    pub fn offset_wrapping(&self, pos: Pos, dx: isize, dy: isize) -> Pos {
+        self.wrap(pos.x as isize + dx, pos.y as isize + dy)
+    }
+
+    /// The position `(x, y)` ends up at when the grid repeats in every direction.
+    pub fn wrap(&self, x: isize, y: isize) -> Pos {
+        Pos::new(
+            x.rem_euclid(self.width as isize) as usize,
+            y.rem_euclid(self.height as isize) as usize,
+        )
+    }
+
+    /// The up to 4 horizontal and vertical neighbours of `pos`.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS4.iter().filter_map(move |&(dx, dy)| self.offset(pos, dx, dy))
+    }
+
+    /// The up to 8 neighbours of `pos`, diagonals included.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS8.iter().filter_map(move |&(dx, dy)| self.offset(pos, dx, dy))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside a grid of width {}", self.width);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos).unwrap_or_else(|| panic!("{pos:?} is outside a {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos).unwrap_or_else(|| panic!("{pos:?} is outside a {width}x{height} grid"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(input, |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parse_works() {
+        let grid = digits("123\n456\n");
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(6, grid[Pos::new(2, 1)]);
+        assert_eq!(None, grid.get(Pos::new(3, 0)));
+        assert_eq!("123\n456", grid.to_string());
+    }
+
+    #[test]
+    fn parse_reports_bad_cells_and_rows() {
+        let err = Grid::parse("12\n3x", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!("line 2, column 2: expected a grid cell, found \"x\"", err.to_string());
+
+        let err = Grid::parse("12\n345", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!("line 2, column 3: expected a row of 2 cells, found \"5\"", err.to_string());
+
+        let err = Grid::parse("123\n45", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!("line 2, column 3: expected a row of 3 cells, found end of input", err.to_string());
+    }
+
+    #[test]
+    fn parse_handles_crlf() {
+        assert_eq!(digits("12\n34"), digits("12\r\n34\r\n"));
+
+        let err = Grid::parse("12\r\n34\r\n5x", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!("line 3, column 2: expected a grid cell, found \"x\"", err.to_string());
+    }
+
+    #[test]
+    fn neighbors_stay_on_grid() {
+        let grid = digits("123\n456\n789");
+        let values = |positions: Vec<Pos>| positions.into_iter().map(|pos| grid[pos]).collect::<Vec<_>>();
+        assert_eq!(vec![2, 4], values(grid.neighbors4(Pos::new(0, 0)).collect()));
+        assert_eq!(vec![6, 8, 4, 2], values(grid.neighbors4(Pos::new(1, 1)).collect()));
+        assert_eq!(vec![2, 5, 4], values(grid.neighbors8(Pos::new(0, 0)).collect()));
+        assert_eq!(8, grid.neighbors8(Pos::new(1, 1)).count());
+    }
+
+    #[test]
+    fn wrapping_works() {
+        let grid = digits("123\n456");
+        assert_eq!(Pos::new(2, 1), grid.wrap(-1, -1));
+        assert_eq!(Pos::new(0, 0), grid.offset_wrapping(Pos::new(2, 1), 1, 1));
+        assert_eq!(None, grid.offset(Pos::new(2, 1), 1, 0));
+    }
+
+    #[test]
+    fn rows_and_columns_work() {
+        let grid = digits("123\n456");
+        assert_eq!(&[4, 5, 6], grid.row(1));
+        assert_eq!(vec![&[1, 2, 3][..], &[4, 5, 6]], grid.rows().collect::<Vec<_>>());
+        assert_eq!(vec![2, 5], grid.column(1).copied().collect::<Vec<_>>());
+        assert_eq!(vec![3, 6], grid.columns().last().unwrap().copied().collect::<Vec<_>>());
+        assert_eq!(vec![Pos::new(1, 0), Pos::new(1, 1)], grid.find(|&v| v % 3 == 2).collect::<Vec<_>>());
+    }
+}
@@ -3,7 +3,7 @@ use std::ops::Range;
 pub fn merge_ranges_in_place<T: Ord + Copy>(ranges: &mut Vec<Range<T>>) {
     ranges.sort_by_key(|r| r.start);
     let mut i = 0;
-    while i < ranges.len() - 1 {
+    while i + 1 < ranges.len() {
         let r1 = &ranges[i];
         let r2 = &ranges[i + 1];
         if r1.end >= r2.start {
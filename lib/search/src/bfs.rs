@@ -0,0 +1,118 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    hash::Hash,
+};
+
+use crate::Reached;
+
+/// Visits nodes in order of steps from `starts` until `success` holds, returning the node
+/// found, if any, and everything reached so far.
+fn breadth_first<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut success: impl FnMut(&N) -> bool,
+) -> (Reached<N, usize>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut reached = Reached::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if reached.improve(start.clone(), 0, None) {
+            queue.push_back((start, 0));
+        }
+    }
+    while let Some((node, steps)) = queue.pop_front() {
+        if success(&node) {
+            return (reached, Some(node));
+        }
+        for next in successors(&node) {
+            if !reached.contains(&next) {
+                reached.improve(next.clone(), steps + 1, Some(node.clone()));
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    (reached, None)
+}
+
+/// The path with the fewest steps from `start` to a node where `success` holds.
+pub fn bfs<N, I>(start: N, successors: impl FnMut(&N) -> I, success: impl FnMut(&N) -> bool) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs_multi([start], successors, success)
+}
+
+/// Like `bfs`, but starting from whichever of `starts` is closest.
+pub fn bfs_multi<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    success: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let (reached, found) = breadth_first(starts, successors, success);
+    reached.path_to(&found?)
+}
+
+/// The number of steps to every node reachable from `starts`.
+pub fn bfs_all<N, I>(starts: impl IntoIterator<Item = N>, successors: impl FnMut(&N) -> I) -> Reached<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    breadth_first(starts, successors, |_| false).0
+}
+
+/// Every node reachable from `starts`, for when neither distances nor paths matter.
+pub fn flood_fill<N, I>(starts: impl IntoIterator<Item = N>, mut successors: impl FnMut(&N) -> I) -> HashSet<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut filled = HashSet::new();
+    let mut stack: Vec<N> = starts.into_iter().collect();
+    while let Some(node) = stack.pop() {
+        if filled.insert(node.clone()) {
+            stack.extend(successors(&node).into_iter().filter(|next| !filled.contains(next)));
+        }
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Steps of +1 or *2, never past 20.
+    fn successors(&n: &u32) -> Vec<u32> {
+        [n + 1, n * 2].into_iter().filter(|&m| m <= 20).collect()
+    }
+
+    #[test]
+    fn bfs_finds_shortest_path() {
+        assert_eq!(Some(vec![1, 2, 4, 5, 10]), bfs(1, successors, |&n| n == 10));
+        assert_eq!(Some(vec![3]), bfs(3, successors, |&n| n == 3));
+        assert_eq!(None, bfs(1, successors, |&n| n == 21));
+    }
+
+    #[test]
+    fn bfs_multi_starts_from_closest() {
+        assert_eq!(Some(vec![9, 18, 19]), bfs_multi([1, 9], successors, |&n| n == 19));
+    }
+
+    #[test]
+    fn bfs_all_and_flood_fill_work() {
+        let reached = bfs_all([10], successors);
+        assert_eq!(11, reached.len());
+        assert_eq!(Some(2), reached.cost(&12));
+        assert_eq!(Some(vec![10, 11, 12]), reached.path_to(&12));
+        assert_eq!(None, reached.cost(&9));
+        assert_eq!((5..=20).collect::<HashSet<_>>(), flood_fill([5], successors));
+    }
+}
@@ -0,0 +1,151 @@
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    hash::Hash,
+    ops::Add,
+};
+
+use crate::Reached;
+
+/// A node waiting in the queue, the one with the lowest `priority` comes out first.
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// A* from `start`, which is Dijkstra with a `heuristic` of zero. Returns the node where
+/// `success` holds, if any, and everything reached so far.
+fn best_first<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut success: impl FnMut(&N) -> bool,
+) -> (Reached<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut reached = Reached::new();
+    let mut queue = BinaryHeap::new();
+    reached.improve(start.clone(), C::default(), None);
+    queue.push(Queued { priority: heuristic(&start), cost: C::default(), node: start });
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        if reached.cost(&node).is_some_and(|best| best < cost) {
+            // reached for less since it was queued
+            continue;
+        }
+        if success(&node) {
+            return (reached, Some(node));
+        }
+        for (next, step) in successors(&node) {
+            let cost = cost + step;
+            if reached.improve(next.clone(), cost, Some(node.clone())) {
+                queue.push(Queued { priority: cost + heuristic(&next), cost, node: next });
+            }
+        }
+    }
+    (reached, None)
+}
+
+/// The cheapest path from `start` to a node where `success` holds, with its cost.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    success: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), success)
+}
+
+/// The cost of the cheapest path to every node reachable from `start`.
+pub fn dijkstra_all<N, C, I>(start: N, successors: impl FnMut(&N) -> I) -> Reached<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(start, successors, |_| C::default(), |_| false).0
+}
+
+/// Like `dijkstra`, but looks at the nodes that `heuristic` deems closer first. The heuristic
+/// must never overestimate the remaining cost, or the path found may not be the cheapest.
+pub fn astar<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    success: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (reached, found) = best_first(start, successors, heuristic, success);
+    let found = found?;
+    Some((reached.path_to(&found)?, reached.cost(&found)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A -1-> B -1-> C -1-> D, with a shortcut A -5-> D and a dead end B -1-> E.
+    fn successors(&node: &char) -> Vec<(char, u32)> {
+        match node {
+            'A' => vec![('B', 1), ('D', 5)],
+            'B' => vec![('C', 1), ('E', 1)],
+            'C' => vec![('D', 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn dijkstra_finds_cheapest_path() {
+        assert_eq!(Some((vec!['A', 'B', 'C', 'D'], 3)), dijkstra('A', successors, |&n| n == 'D'));
+        assert_eq!(None, dijkstra('C', successors, |&n| n == 'A'));
+    }
+
+    #[test]
+    fn dijkstra_all_works() {
+        let reached = dijkstra_all('A', successors);
+        let mut costs: Vec<_> = reached.iter().map(|(&n, c)| (n, c)).collect();
+        costs.sort();
+        assert_eq!(vec![('A', 0), ('B', 1), ('C', 2), ('D', 3), ('E', 2)], costs);
+    }
+
+    #[test]
+    fn astar_works() {
+        // walking a line from 0 to 10 in steps of 1 or 3, where steps of 3 cost 2
+        let successors = |&n: &i32| [(n + 1, 1), (n + 3, 2)].into_iter().filter(|&(m, _)| m <= 10);
+        let (path, cost) = astar(0, successors, |&n| (10 - n) / 3, |&n| n == 10).unwrap();
+        assert_eq!(7, cost);
+        assert_eq!(Some(&0), path.first());
+        assert_eq!(Some(&10), path.last());
+    }
+}
@@ -0,0 +1,10 @@
+//! Graph searches over nodes whose successors come from a closure, so a puzzle never has to
+//! build the graph up front.
+
+mod bfs;
+mod dijkstra;
+mod reached;
+
+pub use bfs::{bfs, bfs_all, bfs_multi, flood_fill};
+pub use dijkstra::{astar, dijkstra, dijkstra_all};
+pub use reached::Reached;
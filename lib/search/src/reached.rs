@@ -0,0 +1,64 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    hash::Hash,
+};
+
+/// The nodes a search got to, with the cost of the cheapest way there and where it came from.
+#[derive(Debug, Clone)]
+pub struct Reached<N, C> {
+    nodes: HashMap<N, (C, Option<N>)>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy + Ord> Reached<N, C> {
+    pub(crate) fn new() -> Self {
+        Reached { nodes: HashMap::new() }
+    }
+
+    /// Records `node` as reached for `cost`, unless it was already reached for less.
+    pub(crate) fn improve(&mut self, node: N, cost: C, parent: Option<N>) -> bool {
+        match self.nodes.entry(node) {
+            Entry::Vacant(entry) => {
+                entry.insert((cost, parent));
+                true
+            }
+            Entry::Occupied(mut entry) if cost < entry.get().0 => {
+                entry.insert((cost, parent));
+                true
+            }
+            Entry::Occupied(_) => false,
+        }
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.nodes.contains_key(node)
+    }
+
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.nodes.get(node).map(|&(cost, _)| cost)
+    }
+
+    /// The nodes from a start to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        let mut path = vec![node.clone()];
+        let mut parent = &self.nodes.get(node)?.1;
+        while let Some(node) = parent {
+            path.push(node.clone());
+            parent = &self.nodes[node].1;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every reached node with its cost, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> {
+        self.nodes.iter().map(|(node, &(cost, _))| (node, cost))
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}